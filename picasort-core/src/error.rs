@@ -20,6 +20,10 @@ pub enum CoreError {
     #[error("EXIF Tag not found")]
     EXIFTagNotFound(),
 
+    /// The copied file content differs from its source
+    #[error("Content mismatch after copy: {0}")]
+    ContentMismatch(String),
+
     /// Standard IO error
     #[error("IO error: {0}")]
     IO(#[from] io::Error),
@@ -28,7 +32,53 @@ pub enum CoreError {
     #[error("Time parse error: {0}")]
     TimeParse(#[from] chrono::ParseError),
 
+    /// JSON (de)serialization error
+    #[cfg(feature = "serde")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Image decoding error
+    #[cfg(feature = "pixels")]
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+
+    /// The thread pool of a parallel scan could not be built
+    #[error("Thread pool error: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
+
+    /// The serialized data has a schema version this crate cannot read
+    #[error("Unsupported schema version: {0}")]
+    UnsupportedSchema(u32),
+
+    /// The file is of a format whose metadata cannot be read, e.g. a PSD
+    #[error("Unsupported format: {0}")]
+    UnsupportedFormat(String),
+
+    /// The file is not a JPEG or its markers are broken
+    #[error("Invalid JPEG: {0}")]
+    InvalidJpeg(String),
+
+    /// The name template has an unknown token or a syntax error
+    #[error("Invalid name template: {0}")]
+    InvalidTemplate(String),
+
+    /// The metadata has no value for a token of the name template
+    #[error("No value for template token: {0}")]
+    MissingTemplateValue(String),
+
     /// Utf8 conversion error
     #[error("UTF-8 conversion error: {0}")]
     Ut8Converion(#[from] FromUtf8Error),
 }
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldError {
+    /// No field has this name
+    #[error("Unknown field: {0}")]
+    UnknownField(String),
+
+    /// The field is not an Option and cannot be cleared
+    #[error("Field is not optional: {0}")]
+    NotOptional(&'static str),
+}
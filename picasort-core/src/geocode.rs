@@ -0,0 +1,250 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use crate::metadata::gps::GPSData;
+
+/// A named place
+#[derive(Debug, Clone, PartialEq)]
+pub struct Place {
+    pub country: String,
+    pub region: Option<String>,
+    pub city: Option<String>,
+}
+
+/// Finds the place of decimal coordinates
+pub trait ReverseGeocoder {
+    fn lookup(&self, lat: f64, lon: f64) -> Option<Place>;
+}
+
+impl GPSData {
+    /// Place of the coordinates, None when they are invalid or unknown
+    pub fn place<G: ReverseGeocoder + ?Sized>(&self, geocoder: &G) -> Option<Place> {
+        let (lat, lon) = self.decimal_coordinates()?;
+        geocoder.lookup(lat, lon)
+    }
+}
+
+#[cfg(feature = "geocoding")]
+pub use offline::{City, DEFAULT_MAX_DISTANCE, OfflineGeocoder};
+
+/// Nearest city lookup in a list of cities, without any network access
+#[cfg(feature = "geocoding")]
+mod offline {
+    use std::collections::HashMap;
+    use std::sync::LazyLock;
+
+    use super::{Place, ReverseGeocoder};
+    use crate::error::CoreError;
+    use crate::metadata::gps::timezone_finder;
+    use crate::values::haversine_distance;
+
+    /// Major cities of the world, "name,region,country,latitude,longitude"
+    const BUNDLED_CITIES: &str = include_str!("../data/cities.csv");
+    /// Country of each IANA timezone, "zone,country"
+    const BUNDLED_ZONE_COUNTRIES: &str = include_str!("../data/zone_countries.csv");
+    /// Distance in meters beyond which a position is not near any city
+    pub const DEFAULT_MAX_DISTANCE: f64 = 50_000.0;
+
+    /// Country of the timezone polygon holding the position, None at sea
+    /// (the Etc/GMT zones) or for a zone without a country
+    fn country_at(lat: f64, lon: f64) -> Option<&'static str> {
+        static ZONE_COUNTRIES: LazyLock<HashMap<&str, &str>> = LazyLock::new(|| {
+            BUNDLED_ZONE_COUNTRIES
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .filter_map(|line| line.split_once(','))
+                .collect()
+        });
+        ZONE_COUNTRIES
+            .get(timezone_finder().get_tz_name(lon, lat))
+            .copied()
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct City {
+        pub name: String,
+        pub region: Option<String>,
+        pub country: String,
+        pub latitude: f64,
+        pub longitude: f64,
+    }
+
+    /// Geocoder returning the nearest city within a maximum distance, else
+    /// only the country, from the timezone polygons bundled with tzf.
+    ///
+    /// The bundled list only holds about 80 major cities, most positions
+    /// farther than `DEFAULT_MAX_DISTANCE` from them get their country
+    /// alone. Load a denser list, e.g. a GeoNames cities1000 extract, with
+    /// `from_csv` for city level results. The country fallback knows the
+    /// country of a timezone, a zone spanning a border region of a
+    /// neighbouring country gives the country of the zone.
+    #[derive(Debug, Clone)]
+    pub struct OfflineGeocoder {
+        cities: Vec<City>,
+        max_distance: f64,
+    }
+
+    impl Default for OfflineGeocoder {
+        /// Geocoder of the bundled major cities
+        fn default() -> Self {
+            OfflineGeocoder::from_csv(BUNDLED_CITIES).expect("Invalid bundled cities")
+        }
+    }
+
+    impl OfflineGeocoder {
+        /// Reads cities as "name,region,country,latitude,longitude" lines, the
+        /// region can be empty. Empty lines and lines starting with '#' are
+        /// skipped.
+        pub fn from_csv(csv: &str) -> Result<OfflineGeocoder, CoreError> {
+            let mut cities = Vec::new();
+            for line in csv.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let invalid = || CoreError::InvalidGPSData(format!("Invalid city: {line}"));
+                let [name, region, country, latitude, longitude] = line
+                    .split(',')
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .map_err(|_| invalid())?;
+                cities.push(City {
+                    name: name.to_string(),
+                    region: Some(region.to_string()).filter(|r| !r.is_empty()),
+                    country: country.to_string(),
+                    latitude: latitude.parse().map_err(|_| invalid())?,
+                    longitude: longitude.parse().map_err(|_| invalid())?,
+                });
+            }
+            Ok(OfflineGeocoder {
+                cities,
+                max_distance: DEFAULT_MAX_DISTANCE,
+            })
+        }
+
+        /// Sets the distance in meters beyond which no city is returned
+        pub fn with_max_distance(mut self, max_distance: f64) -> OfflineGeocoder {
+            self.max_distance = max_distance;
+            self
+        }
+
+        pub fn cities(&self) -> &[City] {
+            &self.cities
+        }
+    }
+
+    impl ReverseGeocoder for OfflineGeocoder {
+        fn lookup(&self, lat: f64, lon: f64) -> Option<Place> {
+            let (city, distance) = self
+                .cities
+                .iter()
+                .map(|c| (c, haversine_distance(lat, lon, c.latitude, c.longitude)))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .unzip();
+            match (city, distance) {
+                (Some(city), Some(distance)) if distance <= self.max_distance => Some(Place {
+                    country: city.country.clone(),
+                    region: city.region.clone(),
+                    city: Some(city.name.clone()),
+                }),
+                _ => country_at(lat, lon).map(|country| Place {
+                    country: country.to_string(),
+                    region: None,
+                    city: None,
+                }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed;
+
+    impl ReverseGeocoder for Fixed {
+        fn lookup(&self, _lat: f64, _lon: f64) -> Option<Place> {
+            Some(Place {
+                country: "France".to_string(),
+                region: None,
+                city: None,
+            })
+        }
+    }
+
+    #[test]
+    fn has_no_place_without_coordinates() {
+        assert_eq!(GPSData::default().place(&Fixed), None);
+    }
+
+    #[cfg(feature = "geocoding")]
+    #[test]
+    fn has_offline_lookup() {
+        use crate::metadata::exif::ExifAssignable;
+        use std::path::Path;
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        let exif = little_exif::metadata::Metadata::new_from_path(&path).unwrap();
+        let mut gps = GPSData::default();
+        gps.assign(&exif).unwrap();
+
+        let geocoder = OfflineGeocoder::default();
+        assert_eq!(
+            gps.place(&geocoder),
+            Some(Place {
+                country: "France".to_string(),
+                region: Some("Auvergne-Rhône-Alpes".to_string()),
+                city: Some("Lyon".to_string()),
+            })
+        );
+        // Middle of the Atlantic
+        assert_eq!(geocoder.lookup(30.0, -40.0), None);
+        // Clermont-Ferrand, 130 km from Lyon
+        assert_eq!(
+            geocoder.lookup(45.7772, 3.0870),
+            Some(Place {
+                country: "France".to_string(),
+                region: None,
+                city: None,
+            })
+        );
+        assert_eq!(
+            geocoder
+                .lookup(-13.9626, 33.7741)
+                .map(|place| place.country),
+            Some("Malawi".to_string())
+        );
+        let geocoder = geocoder.with_max_distance(f64::INFINITY);
+        assert!(geocoder.lookup(30.0, -40.0).is_some());
+    }
+
+    #[cfg(feature = "geocoding")]
+    #[test]
+    fn has_country_of_every_zone() {
+        let finder = crate::metadata::gps::timezone_finder();
+        let unknown: Vec<&str> = finder
+            .finder
+            .timezonenames()
+            .into_iter()
+            .filter(|zone| !zone.starts_with("Etc/"))
+            .filter(|zone| {
+                !include_str!("../data/zone_countries.csv")
+                    .lines()
+                    .any(|line| line.split_once(',').is_some_and(|(z, _)| z == *zone))
+            })
+            .collect();
+        assert!(unknown.is_empty(), "{unknown:?}");
+    }
+
+    #[cfg(feature = "geocoding")]
+    #[test]
+    fn has_csv_cities() {
+        let geocoder =
+            OfflineGeocoder::from_csv("# comment\nMonaco,,Monaco,43.7384,7.4246\n").unwrap();
+        assert_eq!(geocoder.cities().len(), 1);
+        assert_eq!(geocoder.cities()[0].region, None);
+        assert!(OfflineGeocoder::from_csv("Monaco,Monaco,43.7384,7.4246").is_err());
+        assert!(OfflineGeocoder::from_csv("Monaco,,Monaco,north,7.4246").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error::CoreError;
+use crate::metadata::Metadata;
+use crate::scan::list_image_files;
+
+/// Cached metadata of a file with what is used to detect its changes
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexEntry {
+    pub mtime: SystemTime,
+    pub size: u64,
+    pub metadata: Metadata,
+}
+
+/// Counters of an `IndexCache::refresh`
+#[derive(Debug, Default)]
+pub struct RefreshStats {
+    pub extracted: usize,
+    pub reused: usize,
+    pub removed: usize,
+    pub errors: Vec<(PathBuf, CoreError)>,
+}
+
+/// Incremental index: the metadata of a file is only extracted again when
+/// its modification time or size changed since the last refresh.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexCache {
+    entries: HashMap<PathBuf, IndexEntry>,
+}
+
+impl IndexCache {
+    pub fn new() -> IndexCache {
+        IndexCache::default()
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&Metadata> {
+        self.entries.get(path).map(|e| &e.metadata)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Updates the index with the images under `root`. Entries of files that
+    /// disappeared from `root` are dropped.
+    pub fn refresh(&mut self, root: &Path) -> Result<RefreshStats, CoreError> {
+        let mut stats = RefreshStats::default();
+        let files = list_image_files(root, false)?;
+
+        let before = self.entries.len();
+        self.entries
+            .retain(|path, _| !path.starts_with(root) || files.binary_search(path).is_ok());
+        stats.removed = before - self.entries.len();
+
+        for path in files {
+            let (mtime, size) = match fs::metadata(&path).and_then(|m| Ok((m.modified()?, m.len())))
+            {
+                Ok(v) => v,
+                Err(e) => {
+                    stats.errors.push((path, e.into()));
+                    continue;
+                }
+            };
+            if let Some(entry) = self.entries.get(&path)
+                && entry.mtime == mtime
+                && entry.size == size
+            {
+                stats.reused += 1;
+                continue;
+            }
+            match Metadata::from_path(&path) {
+                Ok(metadata) => {
+                    self.entries.insert(
+                        path,
+                        IndexEntry {
+                            mtime,
+                            size,
+                            metadata,
+                        },
+                    );
+                    stats.extracted += 1;
+                }
+                Err(e) => {
+                    self.entries.remove(&path);
+                    stats.errors.push((path, e));
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Writes the index as JSON
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: &Path) -> Result<(), CoreError> {
+        let writer = std::io::BufWriter::new(fs::File::create(path)?);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Reads an index written by `save`
+    #[cfg(feature = "serde")]
+    pub fn load(path: &Path) -> Result<IndexCache, CoreError> {
+        let reader = std::io::BufReader::new(fs::File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    fn temp_library() -> TempDir {
+        let root = TempDir::new();
+        let images = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img");
+        for name in ["text_icon_gps.jpg", "text_car_animal_no-gps.png"] {
+            fs::copy(images.join(name), root.join(name)).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn has_incremental_refresh() {
+        let root = temp_library();
+        let mut cache = IndexCache::new();
+
+        let stats = cache.refresh(&root).unwrap();
+        assert_eq!((stats.extracted, stats.reused, stats.removed), (2, 0, 0));
+        assert!(stats.errors.is_empty());
+
+        let stats = cache.refresh(&root).unwrap();
+        assert_eq!((stats.extracted, stats.reused, stats.removed), (0, 2, 0));
+
+        let changed = root.join("text_icon_gps.jpg");
+        fs::File::options()
+            .write(true)
+            .open(&changed)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        fs::remove_file(root.join("text_car_animal_no-gps.png")).unwrap();
+        let stats = cache.refresh(&root).unwrap();
+        assert_eq!((stats.extracted, stats.reused, stats.removed), (1, 0, 1));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&changed).is_some());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn has_saved_index() {
+        let root = temp_library();
+        let mut cache = IndexCache::new();
+        cache.refresh(&root).unwrap();
+        let cache_path = root.join("index.json");
+        cache.save(&cache_path).unwrap();
+
+        let mut loaded = IndexCache::load(&cache_path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        let stats = loaded.refresh(&root).unwrap();
+        assert_eq!((stats.extracted, stats.reused), (0, 2));
+    }
+}
@@ -1,13 +1,35 @@
 // Copyright (c) 2024 Lemur-Catta.org
 // Author: Sylvain Gubian <sgubian@lemur-catta.org>
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
 use std::any::Any;
+#[cfg(feature = "std")]
 use struct_introspec_macros::DynamicGetSet;
 
+#[cfg(feature = "std")]
 pub mod error;
+#[cfg(feature = "std")]
+pub use error::FieldError;
+#[cfg(feature = "std")]
+pub mod geocode;
+#[cfg(feature = "std")]
 pub mod image;
+#[cfg(feature = "std")]
+pub mod index;
+#[cfg(feature = "std")]
 pub mod metadata;
+#[cfg(feature = "std")]
+pub mod scan;
+#[cfg(feature = "std")]
+pub mod sort;
+#[cfg(feature = "std")]
 pub mod utils;
+pub mod values;
+
+#[cfg(all(test, feature = "std"))]
+mod test_utils;
 
 #[macro_export]
 macro_rules! try_assert {
@@ -18,10 +40,125 @@ macro_rules! try_assert {
     };
 }
 
+#[cfg(feature = "std")]
 pub trait DynamicGetSet {
     fn set_field_by_index(&mut self, index: usize, value: Box<dyn Any>)
     -> Result<(), &'static str>;
     fn set_field_by_name(&mut self, name: &str, value: Box<dyn Any>) -> Result<(), &'static str>;
-    fn get_field_names() -> Vec<&'static str>;
+    fn get_field_names() -> Vec<&'static str>
+    where
+        Self: Sized;
+    /// Names of the non-Option fields and of the Option fields set to `Some`
+    fn populated_field_names(&self) -> Vec<&'static str>;
+    /// Names of the `#[exif(required)]` Option fields set to `None`
+    fn missing_required(&self) -> Vec<&'static str>;
     fn get_value_by_field_name(&self, name: &str) -> Option<&dyn std::any::Any>;
+    /// All fields in declaration order with their values (None for unset options)
+    fn fields(&self) -> Vec<(&'static str, Option<&dyn std::any::Any>)>;
+    /// Sets an `Option` field back to `None`
+    fn clear_field_by_name(&mut self, name: &str) -> Result<(), FieldError>;
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, DynamicGetSet)]
+    struct Empty {}
+
+    #[derive(Debug, Default, DynamicGetSet)]
+    struct Unit;
+
+    #[derive(Debug, Default, DynamicGetSet)]
+    struct Single {
+        value: Option<u32>,
+    }
+
+    #[derive(Debug, Default, DynamicGetSet)]
+    struct Pair {
+        name: String,
+        count: Option<u32>,
+    }
+
+    #[derive(Debug, Default, DynamicGetSet)]
+    struct Required {
+        #[exif(required)]
+        date: Option<String>,
+        label: Option<String>,
+        #[exif(required)]
+        size: Option<u32>,
+    }
+
+    #[test]
+    fn has_empty_struct_derive() {
+        let mut empty = Empty {};
+        assert!(Empty::get_field_names().is_empty());
+        assert!(empty.fields().is_empty());
+        assert!(empty.get_value_by_field_name("any").is_none());
+        assert!(empty.set_field_by_index(0, Box::new(1)).is_err());
+        assert!(empty.set_field_by_name("any", Box::new(1)).is_err());
+        assert_eq!(
+            empty.clear_field_by_name("any"),
+            Err(FieldError::UnknownField("any".to_string()))
+        );
+        assert!(Unit::get_field_names().is_empty());
+        assert!(Unit.fields().is_empty());
+        assert!(Unit.populated_field_names().is_empty());
+    }
+
+    #[test]
+    fn has_single_field_derive() {
+        let mut single = Single::default();
+        assert_eq!(Single::get_field_names(), vec!["value"]);
+        assert!(single.populated_field_names().is_empty());
+        assert!(single.set_field_by_index(0, Box::new(Some(3u32))).is_ok());
+        assert!(single.set_field_by_index(1, Box::new(Some(3u32))).is_err());
+        assert_eq!(
+            single
+                .get_value_by_field_name("value")
+                .and_then(|v| v.downcast_ref::<u32>()),
+            Some(&3)
+        );
+        assert!(single.clear_field_by_name("value").is_ok());
+        assert!(single.value.is_none());
+    }
+
+    #[test]
+    fn has_two_fields_derive() {
+        let mut pair = Pair::default();
+        assert_eq!(Pair::get_field_names(), vec!["name", "count"]);
+        assert!(
+            pair.set_field_by_name("name", Box::new("a".to_string()))
+                .is_ok()
+        );
+        assert!(pair.set_field_by_index(1, Box::new(Some(2u32))).is_ok());
+        assert!(pair.set_field_by_name("count", Box::new(2u32)).is_err());
+        assert_eq!(
+            pair.get_value_by_field_name("name")
+                .and_then(|v| v.downcast_ref::<String>()),
+            Some(&"a".to_string())
+        );
+        assert_eq!(pair.fields().len(), 2);
+        assert_eq!(pair.populated_field_names(), vec!["name", "count"]);
+        assert_eq!(
+            pair.clear_field_by_name("name"),
+            Err(FieldError::NotOptional("name"))
+        );
+        assert!(pair.clear_field_by_name("count").is_ok());
+        assert_eq!(pair.populated_field_names(), vec!["name"]);
+        assert!(pair.get_value_by_field_name("count").is_none());
+    }
+
+    #[test]
+    fn has_missing_required() {
+        let mut required = Required::default();
+        assert_eq!(required.missing_required(), vec!["date", "size"]);
+        required.size = Some(3);
+        required.label = Some("a".to_string());
+        assert_eq!(required.missing_required(), vec!["date"]);
+        required.date = Some("2024".to_string());
+        assert!(required.missing_required().is_empty());
+        assert!(Pair::default().missing_required().is_empty());
+        assert!(Unit.missing_required().is_empty());
+    }
 }
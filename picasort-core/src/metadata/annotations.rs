@@ -0,0 +1,266 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+// Rating and color label set by the user in a photo manager, from the EXIF,
+// the XMP embedded in the file and its XMP sidecar.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use little_exif::{exif_tag::ExifTag, ifd::ExifTagGroup};
+
+use crate::DynamicGetSet;
+use crate::error::CoreError;
+use crate::metadata::exif::{
+    ExifAssignable, ExifExtractable, ExtractionSet, TagContext, decode_utf16_with,
+    extract_unsigned_int16,
+};
+use crate::metadata::format::ImageFormat;
+use crate::metadata::xmp::{xmp_packet, xmp_property};
+
+/// Microsoft Rating (0x4746), not known by little_exif
+const RATING_TAG: u16 = 0x4746;
+/// Microsoft XPKeywords (0x9c9e), UTF-16LE keywords separated by ';'
+const XP_KEYWORDS_TAG: u16 = 0x9c9e;
+/// Highest number of stars, a larger rating is ignored
+const MAX_RATING: usize = 5;
+
+/// Color label of Lightroom, Bridge or digiKam
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorLabel {
+    Red,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    /// Custom label name
+    Other(String),
+}
+
+impl ColorLabel {
+    /// Label of its name (case insensitive), None when it is empty
+    pub fn parse(label: &str) -> Option<ColorLabel> {
+        let label = label.trim();
+        Some(match label.to_ascii_lowercase().as_str() {
+            "" => return None,
+            "red" => ColorLabel::Red,
+            "yellow" => ColorLabel::Yellow,
+            "green" => ColorLabel::Green,
+            "blue" => ColorLabel::Blue,
+            "purple" => ColorLabel::Purple,
+            _ => ColorLabel::Other(label.to_string()),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ColorLabel::Red => "Red",
+            ColorLabel::Yellow => "Yellow",
+            ColorLabel::Green => "Green",
+            ColorLabel::Blue => "Blue",
+            ColorLabel::Purple => "Purple",
+            ColorLabel::Other(name) => name,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, DynamicGetSet)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Annotations {
+    /// Stars from 0 to 5
+    pub rating: Option<usize>,
+    pub label: Option<ColorLabel>,
+}
+
+impl Annotations {
+    /// Rating from the EXIF Rating tag when it is at most 5, label from the
+    /// first color name of the XPKeywords
+    pub fn from_exif(exif: &little_exif::metadata::Metadata) -> Result<Annotations, CoreError> {
+        let mut annotations = Annotations::default();
+        annotations
+            .assign(exif)
+            .map_err(|e| CoreError::InvalidEXIFConversion(e.to_string()))?;
+        annotations.rating = annotations.rating.filter(|r| *r <= MAX_RATING);
+        let keywords_tag =
+            ExifTag::UnknownINT8U(Vec::new(), XP_KEYWORDS_TAG, ExifTagGroup::GENERIC);
+        if let Some(keywords) = Vec::<u8>::extract(&keywords_tag, exif) {
+            annotations.label = decode_utf16_with(&keywords, false)
+                .split(';')
+                .filter_map(ColorLabel::parse)
+                .find(|label| !matches!(label, ColorLabel::Other(_)));
+        }
+        Ok(annotations)
+    }
+
+    /// Overrides the values with the `xmp:Rating` and `xmp:Label` of an XMP
+    /// packet, in attribute or element form. A negative rating (rejected) or
+    /// one above 5 is ignored.
+    pub fn apply_xmp(&mut self, xmp: &str) {
+        if let Some(rating) = xmp_property(xmp, "xmp:Rating")
+            .and_then(|r| r.parse().ok())
+            .filter(|r| *r <= MAX_RATING)
+        {
+            self.rating = Some(rating);
+        }
+        if let Some(label) = xmp_property(xmp, "xmp:Label") {
+            self.label = ColorLabel::parse(&label);
+        }
+    }
+}
+
+impl<'a> ExifAssignable<'a> for Annotations {
+    fn exif_set(&self) -> Option<ExtractionSet<'a>> {
+        Some(ExtractionSet {
+            tags: vec![TagContext {
+                destination: "rating",
+                main_tag: ExifTag::UnknownINT16U(Vec::new(), RATING_TAG, ExifTagGroup::GENERIC),
+                alternative: None,
+                convert: extract_unsigned_int16,
+            }],
+        })
+    }
+}
+
+/// XMP sidecar of an image: "IMG_0001.xmp" as written by Lightroom, else
+/// "IMG_0001.jpg.xmp" as written by darktable. The extension is case
+/// insensitive.
+pub fn xmp_sidecar_path(image: &Path) -> Option<PathBuf> {
+    let name = image.file_name()?.to_str()?;
+    let stem = image.file_stem()?.to_str()?;
+    let dir = image.parent()?;
+    [stem, name]
+        .into_iter()
+        .flat_map(|base| [format!("{base}.xmp"), format!("{base}.XMP")])
+        .map(|candidate| dir.join(candidate))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Rating and label of an image: from its EXIF, overridden by its embedded
+/// XMP, overridden by its XMP sidecar
+pub fn read_annotations<P: AsRef<Path>>(path: P) -> Result<Annotations, CoreError> {
+    let path = path.as_ref();
+    let data = fs::read(path)?;
+    let mut annotations = match ImageFormat::detect(&data) {
+        Some(format) => {
+            let exif =
+                little_exif::metadata::Metadata::new_from_vec(&data, format.file_extension())?;
+            Annotations::from_exif(&exif)?
+        }
+        None => Annotations::default(),
+    };
+    if let Some(xmp) = xmp_packet(&data) {
+        annotations.apply_xmp(xmp);
+    }
+    if let Some(sidecar) = xmp_sidecar_path(path) {
+        annotations.apply_xmp(&fs::read_to_string(sidecar)?);
+    }
+    Ok(annotations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Red", Some(ColorLabel::Red))]
+    #[case(" purple ", Some(ColorLabel::Purple))]
+    #[case("To Print", Some(ColorLabel::Other("To Print".to_string())))]
+    #[case("", None)]
+    fn has_parsed_color_label(#[case] label: &str, #[case] expected: Option<ColorLabel>) {
+        assert_eq!(ColorLabel::parse(label), expected);
+    }
+
+    #[test]
+    fn has_exif_annotations() {
+        let mut exif = little_exif::metadata::Metadata::new();
+        exif.set_tag(ExifTag::UnknownINT16U(
+            vec![4],
+            RATING_TAG,
+            ExifTagGroup::GENERIC,
+        ));
+        let keywords: Vec<u8> = "travel;Green;\0"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        exif.set_tag(ExifTag::UnknownINT8U(
+            keywords,
+            XP_KEYWORDS_TAG,
+            ExifTagGroup::GENERIC,
+        ));
+        let annotations = Annotations::from_exif(&exif).unwrap();
+        assert_eq!(annotations.rating, Some(4));
+        assert_eq!(annotations.label, Some(ColorLabel::Green));
+
+        exif.set_tag(ExifTag::UnknownINT16U(
+            vec![99],
+            RATING_TAG,
+            ExifTagGroup::GENERIC,
+        ));
+        assert_eq!(Annotations::from_exif(&exif).unwrap().rating, None);
+
+        let empty = Annotations::from_exif(&little_exif::metadata::Metadata::new()).unwrap();
+        assert_eq!(empty, Annotations::default());
+    }
+
+    #[test]
+    fn has_xmp_annotations() {
+        let mut annotations = Annotations {
+            rating: Some(2),
+            label: Some(ColorLabel::Blue),
+        };
+        annotations.apply_xmp(r#"<rdf:Description xmp:Rating="5" xmp:Label="Red"/>"#);
+        assert_eq!(annotations.rating, Some(5));
+        assert_eq!(annotations.label, Some(ColorLabel::Red));
+
+        annotations.apply_xmp("<xmp:Label>To &amp; Fro</xmp:Label><xmp:Rating>-1</xmp:Rating>");
+        assert_eq!(annotations.rating, Some(5));
+        annotations.apply_xmp(r#"xmp:Rating="6""#);
+        assert_eq!(annotations.rating, Some(5));
+        assert_eq!(
+            annotations.label,
+            Some(ColorLabel::Other("To & Fro".to_string()))
+        );
+        annotations.apply_xmp(r#"xmp:Label="""#);
+        assert_eq!(annotations.label, None);
+    }
+
+    #[test]
+    fn has_annotations_with_sidecar() {
+        let root = TempDir::new();
+        let image = root.join("IMG_0001.jpg");
+        fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg"),
+            &image,
+        )
+        .unwrap();
+        assert_eq!(xmp_sidecar_path(&image), None);
+        assert_eq!(read_annotations(&image).unwrap(), Annotations::default());
+
+        fs::write(
+            root.join("IMG_0001.jpg.xmp"),
+            r#"<x:xmpmeta><rdf:Description xmp:Rating="3" xmp:Label="Yellow"/></x:xmpmeta>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            xmp_sidecar_path(&image),
+            Some(root.join("IMG_0001.jpg.xmp"))
+        );
+        assert_eq!(
+            read_annotations(&image).unwrap(),
+            Annotations {
+                rating: Some(3),
+                label: Some(ColorLabel::Yellow),
+            }
+        );
+        // The Lightroom name comes first
+        fs::write(root.join("IMG_0001.XMP"), "<xmp:Label>Purple</xmp:Label>").unwrap();
+        assert_eq!(
+            read_annotations(&image).unwrap().label,
+            Some(ColorLabel::Purple)
+        );
+        assert!(read_annotations(root.join("missing.jpg")).is_err());
+    }
+}
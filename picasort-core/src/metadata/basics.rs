@@ -1,62 +1,351 @@
 // Copyright (c) 2026 Lemur-Catta.org
 // Author: Sylvain Gubian <sgubian@lemur-catta.org>
 
+use crate::DynamicGetSet;
 use crate::metadata::exif::{
-    extract_orientation, extract_string, extract_unsigned_int16, extract_unsigned_int32,
-    extract_utc_datetime, ExifAssignable, ExtractionSet, TagContext,
+    ExifAssignable, ExtractedValue, ExtractionSet, TagContext, extract_orientation,
+    extract_rounded_rational, extract_string, extract_unsigned_int16, extract_unsigned_int32,
 };
-use crate::DynamicGetSet;
-use chrono::{DateTime, Utc};
+use crate::metadata::tags;
+pub use crate::values::{DayPart, Hemisphere, Layout, Orientation, OrientationTransform, Season};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, TimeDelta, Timelike, Utc};
 
 use little_exif::exif_tag::ExifTag;
+use little_exif::ifd::ExifTagGroup;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub enum Orientation {
-    Normal,
-    FlippedHorizontally,
-    Rotated180Deg,
-    FlippedVertically,
-    Rotated90DegCCWFlippedVertically,
-    Rotated90DegCW,
-    Rotated90DegCCWPFlippedHorizontally,
-    Rotated90DegCCW,
-    Unknown,
-}
-
-impl Orientation {
-    pub fn from_code(code: u16) -> Orientation {
-        match code {
-            1 => Orientation::Normal,
-            2 => Orientation::FlippedHorizontally,
-            3 => Orientation::Rotated180Deg,
-            4 => Orientation::FlippedVertically,
-            5 => Orientation::Rotated90DegCCWFlippedVertically,
-            6 => Orientation::Rotated90DegCCW,
-            7 => Orientation::Rotated90DegCCWPFlippedHorizontally,
-            8 => Orientation::Rotated90DegCCW,
-            _ => Orientation::Unknown,
-        }
-    }
-
-    // Here, the function takes ownership
-    pub fn code(self) -> u16 {
-        self as u16
-    }
-}
-
-#[derive(Debug, Default, DynamicGetSet)]
+#[derive(Debug, Default, Clone, PartialEq, DynamicGetSet)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Basics {
     pub width: Option<usize>,
     pub height: Option<usize>,
-    pub desciption: Option<String>,
+    pub description: Option<String>,
     pub resolution_x: Option<usize>,
     pub resolution_y: Option<usize>,
     pub resolution_unit: Option<usize>,
     pub orientation: Option<Orientation>,
+    // The EXIF dates are the local time of the camera LABELLED AS UTC, they
+    // are not UTC times. `best_date_with_offset` and `best_utc_date` give the
+    // actual time when the offset is known.
+    /// EXIF DateTimeDigitized (tag 0x9004, little_exif CreateDate), when the
+    /// image was digitized, e.g. the scan time of a film, or the creation
+    /// time of non-EXIF formats
     pub creation_date: Option<DateTime<Utc>>,
     pub original_date: Option<DateTime<Utc>>,
     pub modification_date: Option<DateTime<Utc>>,
+    /// Offset from UTC of the original date (OffsetTimeOriginal)
+    #[cfg_attr(feature = "serde", serde(default, with = "optional_offset"))]
+    pub original_offset: Option<FixedOffset>,
+    /// Offset from UTC of the digitized and creation dates (OffsetTimeDigitized)
+    #[cfg_attr(feature = "serde", serde(default, with = "optional_offset"))]
+    pub digitized_offset: Option<FixedOffset>,
+    /// Offset from UTC of the modification date (OffsetTime)
+    #[cfg_attr(feature = "serde", serde(default, with = "optional_offset"))]
+    pub modification_offset: Option<FixedOffset>,
     pub copyright: Option<String>,
+    pub artist: Option<String>,
+    pub software: Option<String>,
+    pub processing_software: Option<String>,
+    pub image_unique_id: Option<String>,
+    pub make: Option<String>,
+    pub model: Option<String>,
+}
+
+/// Where the width and height are read from, see `Basics::assign_dimensions`.
+/// Editors often keep the EXIF dimensions written by the camera: a cropped or
+/// resized photo can report its original size, only `Pixels` is always right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DimensionSource {
+    /// EXIF ImageWidth and ImageLength
+    ImageTag,
+    /// EXIF PixelXDimension and PixelYDimension
+    ExifImageTag,
+    /// Header of the encoded image, needs the `pixels` feature
+    Pixels,
+}
+
+impl DimensionSource {
+    /// The EXIF tags in the order used by `assign` then the pixels, or the
+    /// pixels first with `prefer_actual`
+    pub fn order(prefer_actual: bool) -> [DimensionSource; 3] {
+        use DimensionSource::*;
+        match prefer_actual {
+            true => [Pixels, ImageTag, ExifImageTag],
+            false => [ImageTag, ExifImageTag, Pixels],
+        }
+    }
+
+    /// Width and height given by this source, None if it has not both
+    pub fn dimensions(
+        self,
+        exif: &little_exif::metadata::Metadata,
+        data: &[u8],
+    ) -> Option<(usize, usize)> {
+        let (width_tag, height_tag) = match self {
+            DimensionSource::ImageTag => (
+                ExifTag::ImageWidth(Vec::new()),
+                ExifTag::ImageHeight(Vec::new()),
+            ),
+            DimensionSource::ExifImageTag => (
+                ExifTag::ExifImageWidth(Vec::new()),
+                ExifTag::ExifImageHeight(Vec::new()),
+            ),
+            #[cfg(feature = "pixels")]
+            DimensionSource::Pixels => {
+                let (width, height) = crate::utils::pixels::image_dimensions(data).ok()?;
+                return Some((width as usize, height as usize));
+            }
+            #[cfg(not(feature = "pixels"))]
+            DimensionSource::Pixels => {
+                let _ = data;
+                return None;
+            }
+        };
+        match (
+            extract_unsigned_int32(&width_tag, exif)?,
+            extract_unsigned_int32(&height_tag, exif)?,
+        ) {
+            (ExtractedValue::UnsignedInt(width), ExtractedValue::UnsignedInt(height)) => {
+                Some((width, height))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// chrono does not serialize `FixedOffset`, it is written as "+01:00"
+#[cfg(feature = "serde")]
+mod optional_offset {
+    use chrono::FixedOffset;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(
+        offset: &Option<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match offset {
+            Some(offset) => serializer.serialize_some(&offset.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<FixedOffset>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|offset| offset.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Earliest year a photograph can have been taken
+const FIRST_PHOTOGRAPH_YEAR: i32 = 1826;
+
+/// Oldest of the creation, original and modification dates that
+/// passes `is_plausible_date`. Unlike `Basics::best_date`, which follows a
+/// precedence, it gives the true capture time of a file re-saved later.
+pub fn earliest_date(basics: &Basics) -> Option<DateTime<Utc>> {
+    [
+        basics.creation_date,
+        basics.original_date,
+        basics.modification_date,
+    ]
+    .into_iter()
+    .flatten()
+    .filter(is_plausible_date)
+    .min()
+}
+
+/// Company words dropped from the end of a make, e.g. "NIKON CORPORATION"
+const MAKE_SUFFIXES: [&str; 11] = [
+    "corporation",
+    "corp.",
+    "corp",
+    "co.,ltd.",
+    "co.,ltd",
+    "co.",
+    "ltd.",
+    "company",
+    "inc.",
+    "imaging",
+    "gmbh",
+];
+
+/// Trims the whitespaces and the NUL padding of a tag value, None when empty
+fn trim_tag(value: Option<&str>) -> Option<&str> {
+    Some(value?.trim_matches(|c: char| c.is_whitespace() || c == '\0')).filter(|v| !v.is_empty())
+}
+
+/// Make without its company suffixes, all uppercase or lowercase words being
+/// title cased: "NIKON CORPORATION" gives "Nikon"
+fn canonical_make(make: &str) -> String {
+    let mut words: Vec<&str> = make.split_whitespace().collect();
+    while words.len() > 1
+        && words
+            .last()
+            .is_some_and(|w| MAKE_SUFFIXES.contains(&w.to_lowercase().as_str()))
+    {
+        words.pop();
+    }
+    let title_case = |word: &str| {
+        let mixed = word.chars().any(char::is_uppercase) && word.chars().any(char::is_lowercase);
+        if mixed {
+            return word.to_string();
+        }
+        let mut chars = word.chars();
+        chars.next().map_or_else(String::new, |first| {
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect()
+        })
+    };
+    words
+        .into_iter()
+        .map(title_case)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Tells if a date can be a capture date: not before the first photograph
+/// and not in the future
+pub fn is_plausible_date(date: &DateTime<Utc>) -> bool {
+    date.year() >= FIRST_PHOTOGRAPH_YEAR && *date <= Utc::now()
+}
+
+impl Basics {
+    /// Make and model, the make being omitted when the model starts with it
+    pub fn camera_name(&self) -> Option<String> {
+        let make = self.make.as_deref().map(str::trim);
+        match (make, self.model.as_deref().map(str::trim)) {
+            (Some(make), Some(model)) if !model.starts_with(make) => {
+                Some(format!("{make} {model}"))
+            }
+            (_, Some(model)) => Some(model.to_string()),
+            (Some(make), None) => Some(make.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// Normalized make and model, e.g. "Nikon Z6 II" for "NIKON CORPORATION"
+    /// and "NIKON Z6 II": trimmed, the make without its company suffixes and
+    /// title cased, the model without the make it may repeat. The raw fields
+    /// are left unchanged.
+    pub fn canonical_camera(&self) -> Option<String> {
+        let raw_make = trim_tag(self.make.as_deref());
+        let make = raw_make.map(canonical_make);
+        let model = trim_tag(self.model.as_deref()).map(|model| {
+            [raw_make, make.as_deref()]
+                .into_iter()
+                .flatten()
+                .find_map(|prefix| {
+                    let head = model.get(..prefix.len())?;
+                    let rest = model[prefix.len()..].trim_start();
+                    let separated = rest.len() < model.len() - prefix.len();
+                    (head.to_lowercase() == prefix.to_lowercase() && separated && !rest.is_empty())
+                        .then_some(rest)
+                })
+                .unwrap_or(model)
+        });
+        match (make, model) {
+            (Some(make), Some(model)) => Some(format!("{make} {model}")),
+            (make, model) => make.or(model.map(str::to_string)),
+        }
+    }
+
+    /// Sets the width and height from the first of `sources` giving both,
+    /// returns false and keeps them unchanged if none does
+    pub fn assign_dimensions(
+        &mut self,
+        exif: &little_exif::metadata::Metadata,
+        data: &[u8],
+        sources: &[DimensionSource],
+    ) -> bool {
+        let Some((width, height)) = sources
+            .iter()
+            .find_map(|source| source.dimensions(exif, data))
+        else {
+            return false;
+        };
+        self.width = Some(width);
+        self.height = Some(height);
+        true
+    }
+
+    /// Width and height as displayed, once the orientation is applied
+    pub fn display_dimensions(&self) -> Option<(usize, usize)> {
+        let (width, height) = (self.width?, self.height?);
+        match self.orientation {
+            Some(o) if o.swaps_dimensions() => Some((height, width)),
+            _ => Some((width, height)),
+        }
+    }
+
+    /// Portrait, landscape or square once the orientation is applied, None
+    /// when the dimensions are unknown
+    pub fn layout(&self) -> Option<Layout> {
+        let (width, height) = self.display_dimensions()?;
+        Layout::from_dimensions(width, height)
+    }
+
+    /// Returns the most trustworthy date: original, then creation (digitized),
+    /// then modification. It is the camera local time labelled as
+    /// UTC, see `best_utc_date` for the actual UTC time.
+    pub fn best_date(&self) -> Option<DateTime<Utc>> {
+        self.best_date_and_offset().map(|(date, _)| date)
+    }
+
+    /// The best date as the camera local time, without the misleading UTC label
+    pub fn best_local_date(&self) -> Option<NaiveDateTime> {
+        self.best_date().map(|date| date.naive_utc())
+    }
+
+    /// The best date with its offset from UTC, None when the offset is unknown
+    pub fn best_date_with_offset(&self) -> Option<DateTime<FixedOffset>> {
+        let (date, offset) = self.best_date_and_offset()?;
+        date.naive_utc().and_local_timezone(offset?).single()
+    }
+
+    /// The actual UTC time of the best date, None when its offset is unknown
+    pub fn best_utc_date(&self) -> Option<DateTime<Utc>> {
+        self.best_date_with_offset().map(|date| date.to_utc())
+    }
+
+    fn best_date_and_offset(&self) -> Option<(DateTime<Utc>, Option<FixedOffset>)> {
+        [
+            (self.original_date, self.original_offset),
+            (self.creation_date, self.digitized_offset),
+            (self.modification_date, self.modification_offset),
+        ]
+        .into_iter()
+        .find_map(|(date, offset)| Some((date?, offset)))
+    }
+
+    /// Shifts all the dates by `offset`, negative for a camera clock ahead.
+    /// A date that would overflow is left unchanged.
+    pub fn shift(&mut self, offset: TimeDelta) {
+        for date in [
+            &mut self.creation_date,
+            &mut self.original_date,
+            &mut self.modification_date,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if let Some(shifted) = date.checked_add_signed(offset) {
+                *date = shifted;
+            }
+        }
+    }
+
+    /// Season of the best date. EXIF dates are local times, so is the season.
+    pub fn season(&self, hemisphere: Hemisphere) -> Option<Season> {
+        Season::from_month(self.best_date()?.month(), hemisphere)
+    }
+
+    /// Part of the day of the best date, in the local time of the camera
+    pub fn time_of_day(&self) -> Option<DayPart> {
+        DayPart::from_hour(self.best_date()?.hour())
+    }
 }
 
 impl<'a> ExifAssignable<'a> for Basics {
@@ -85,13 +374,13 @@ impl<'a> ExifAssignable<'a> for Basics {
                     destination: "resolution_x",
                     main_tag: ExifTag::XResolution(Vec::new()),
                     alternative: None,
-                    convert: extract_unsigned_int32,
+                    convert: extract_rounded_rational,
                 },
                 TagContext {
                     destination: "resolution_y",
                     main_tag: ExifTag::YResolution(Vec::new()),
                     alternative: None,
-                    convert: extract_unsigned_int32,
+                    convert: extract_rounded_rational,
                 },
                 TagContext {
                     destination: "resolution_unit",
@@ -105,30 +394,30 @@ impl<'a> ExifAssignable<'a> for Basics {
                     alternative: None,
                     convert: extract_orientation,
                 },
+                tags::DATE_CREATED,
+                tags::DATE_ORIGINAL,
+                tags::DATE_MODIFIED,
+                tags::OFFSET_ORIGINAL,
+                tags::OFFSET_DIGITIZED,
+                tags::OFFSET_MODIFIED,
+                tags::COPYRIGHT,
+                tags::ARTIST,
+                tags::SOFTWARE,
                 TagContext {
-                    destination: "creation_date",
-                    main_tag: ExifTag::CreateDate(String::new()),
-                    alternative: None,
-                    convert: extract_utc_datetime,
-                },
-                TagContext {
-                    destination: "original_date",
-                    main_tag: ExifTag::DateTimeOriginal(String::new()),
-                    alternative: None,
-                    convert: extract_utc_datetime,
-                },
-                TagContext {
-                    destination: "modification_date",
-                    main_tag: ExifTag::ModifyDate(String::new()),
+                    // ProcessingSoftware (0x000b) is not known by little_exif
+                    destination: "processing_software",
+                    main_tag: ExifTag::UnknownSTRING(String::new(), 0x000b, ExifTagGroup::GENERIC),
                     alternative: None,
-                    convert: extract_utc_datetime,
+                    convert: extract_string,
                 },
                 TagContext {
-                    destination: "copyright",
-                    main_tag: ExifTag::Copyright(String::new()),
+                    destination: "image_unique_id",
+                    main_tag: ExifTag::ImageUniqueID(String::new()),
                     alternative: None,
                     convert: extract_string,
                 },
+                tags::CAMERA_MAKE,
+                tags::CAMERA_MODEL,
             ],
         })
     }
@@ -139,7 +428,7 @@ impl<'a> ExifAssignable<'a> for Basics {
 mod tests {
 
     use crate::metadata::{
-        basics::{Basics, Orientation},
+        basics::{Basics, DayPart, DimensionSource, Hemisphere, Layout, Orientation, Season},
         exif::ExifAssignable,
     };
     use chrono::DateTime;
@@ -153,6 +442,58 @@ mod tests {
         little_exif::metadata::Metadata::new_from_path(&image_path).unwrap()
     }
 
+    #[rstest]
+    #[case(
+        [None, Some("2024-10-28T20:35:03Z"), Some("2023-05-01T10:00:00Z")],
+        Some("2023-05-01T10:00:00Z")
+    )]
+    #[case(
+        [Some("1700-01-01T00:00:00Z"), Some("2024-10-28T20:35:03Z"), Some("2024-10-29T08:00:00Z")],
+        Some("2024-10-28T20:35:03Z")
+    )]
+    #[case([Some("2999-01-01T00:00:00Z"), None, None], None)]
+    #[case([None, None, None], None)]
+    fn has_earliest_date(#[case] dates: [Option<&str>; 3], #[case] expected: Option<&str>) {
+        use crate::metadata::basics::earliest_date;
+
+        let parse = |d: Option<&str>| d.map(|d| DateTime::parse_from_rfc3339(d).unwrap().to_utc());
+        let basics = Basics {
+            creation_date: parse(dates[0]),
+            original_date: parse(dates[1]),
+            modification_date: parse(dates[2]),
+            ..Default::default()
+        };
+        assert_eq!(earliest_date(&basics), parse(expected));
+    }
+
+    #[rstest]
+    #[case(Some("NIKON CORPORATION"), Some("NIKON Z6 II"), Some("Nikon Z6 II"))]
+    #[case(Some("Canon"), Some("Canon EOS R5\0\0"), Some("Canon EOS R5"))]
+    #[case(Some("  SONY "), Some("ILCE-7M3"), Some("Sony ILCE-7M3"))]
+    #[case(Some("OLYMPUS IMAGING CORP."), Some("E-M5"), Some("Olympus E-M5"))]
+    #[case(Some("Apple"), Some("iPhone 12 Pro"), Some("Apple iPhone 12 Pro"))]
+    #[case(
+        Some("FUJIFILM"),
+        Some("FUJIFILMX100V"),
+        Some("Fujifilm FUJIFILMX100V")
+    )]
+    #[case(Some("GoPro"), None, Some("GoPro"))]
+    #[case(Some("\0"), Some("HERO9 Black"), Some("HERO9 Black"))]
+    #[case(None, None, None)]
+    fn has_canonical_camera(
+        #[case] make: Option<&str>,
+        #[case] model: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let basics = Basics {
+            make: make.map(str::to_string),
+            model: model.map(str::to_string),
+            ..Default::default()
+        };
+        assert_eq!(basics.canonical_camera().as_deref(), expected);
+        assert_eq!(basics.make.as_deref(), make);
+    }
+
     #[rstest]
     #[case(
         "text_car_animal_no-gps.png",
@@ -163,9 +504,9 @@ mod tests {
         350,
         3,
         Orientation::Normal,
-        Some("2024-12-27T15:58:43Z"),
-        Some("2024-12-27T15:58:43Z"),
-        Some("2025-11-02T10:45:59Z")
+        Some("2024-12-27T15:58:43.101Z"),
+        Some("2024-12-27T15:58:43.101Z"),
+        Some("2025-11-02T10:45:59.101Z")
     )]
     #[case(
         "text_icon_gps.jpg",
@@ -175,9 +516,9 @@ mod tests {
         72,
         72,
         2,
-        Orientation::Rotated90DegCCW,
-        Some("2024-10-28T20:35:03Z"),
-        Some("2024-10-28T20:35:03Z"),
+        Orientation::Rotated90DegCW,
+        Some("2024-10-28T20:35:03.383Z"),
+        Some("2024-10-28T20:35:03.383Z"),
         Some("2024-10-28T20:35:03Z")
     )]
     fn has_basics(
@@ -202,7 +543,7 @@ mod tests {
         assert_eq!(basics.width, Some(width));
         assert_eq!(basics.height, Some(height));
         assert_eq!(basics.height, Some(height));
-        assert_eq!(basics.desciption, desc);
+        assert_eq!(basics.description, desc);
         assert_eq!(basics.resolution_x, Some(xres));
         assert_eq!(basics.resolution_y, Some(yres));
         assert_eq!(basics.resolution_unit, Some(res_unit));
@@ -227,4 +568,291 @@ mod tests {
             );
         }
     }
+
+    #[rstest]
+    #[case("text_car_animal_no-gps.png", Some("GIMP 2.10.38"))]
+    #[case("text_icon_gps.jpg", Some("18.0.1"))]
+    fn has_software(#[case] filename: &str, #[case] software: Option<&str>) {
+        let metadata = get_metadata(filename);
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        assert_eq!(basics.software.as_deref(), software);
+        assert_eq!(basics.processing_software, None);
+    }
+
+    #[test]
+    fn has_processing_software() {
+        use little_exif::{exif_tag::ExifTag, ifd::ExifTagGroup};
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::UnknownSTRING(
+            "Picasort".to_string(),
+            0x000b,
+            ExifTagGroup::GENERIC,
+        ));
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        assert_eq!(basics.processing_software.as_deref(), Some("Picasort"));
+    }
+
+    #[rstest]
+    #[case(72, 1, Some(72))]
+    #[case(720000, 10000, Some(72))]
+    #[case(1001, 10, Some(100))]
+    #[case(72, 0, None)]
+    fn has_rational_resolution(
+        #[case] nominator: u32,
+        #[case] denominator: u32,
+        #[case] expected: Option<usize>,
+    ) {
+        use little_exif::{exif_tag::ExifTag, rational::uR64};
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        let resolution = vec![uR64 {
+            nominator,
+            denominator,
+        }];
+        metadata.set_tag(ExifTag::XResolution(resolution.clone()));
+        metadata.set_tag(ExifTag::YResolution(resolution));
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        assert_eq!(basics.resolution_x, expected);
+        assert_eq!(basics.resolution_y, expected);
+    }
+
+    #[test]
+    fn has_image_unique_id() {
+        use little_exif::exif_tag::ExifTag;
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::ImageUniqueID(
+            "0123456789abcdef0123456789abcdef".to_string(),
+        ));
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        assert_eq!(
+            basics.image_unique_id.as_deref(),
+            Some("0123456789abcdef0123456789abcdef")
+        );
+    }
+
+    #[rstest]
+    #[case("text_car_animal_no-gps.png", Some((1024, 769)))]
+    #[case("text_icon_gps.jpg", Some((2160, 3840)))]
+    fn has_display_dimensions(#[case] filename: &str, #[case] expected: Option<(usize, usize)>) {
+        let metadata = get_metadata(filename);
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        assert_eq!(basics.display_dimensions(), expected);
+    }
+
+    #[rstest]
+    #[case(Some((4000, 3000)), None, Some(Layout::Landscape))]
+    #[case(Some((4000, 3000)), Some(Orientation::Rotated90DegCW), Some(Layout::Portrait))]
+    #[case(Some((3000, 4000)), Some(Orientation::Normal), Some(Layout::Portrait))]
+    #[case(Some((1000, 960)), None, Some(Layout::Square))]
+    #[case(Some((1000, 940)), None, Some(Layout::Landscape))]
+    #[case(Some((0, 1000)), None, None)]
+    #[case(None, None, None)]
+    fn has_layout(
+        #[case] dimensions: Option<(usize, usize)>,
+        #[case] orientation: Option<Orientation>,
+        #[case] expected: Option<Layout>,
+    ) {
+        let basics = Basics {
+            width: dimensions.map(|(w, _)| w),
+            height: dimensions.map(|(_, h)| h),
+            orientation,
+            ..Default::default()
+        };
+        assert_eq!(basics.layout(), expected);
+    }
+
+    #[rstest]
+    #[case("text_car_animal_no-gps.png", "SONY", "ILCE-7M4")]
+    #[case("text_icon_gps.jpg", "Apple", "iPhone 12 Pro")]
+    fn has_camera(#[case] filename: &str, #[case] make: &str, #[case] model: &str) {
+        let metadata = get_metadata(filename);
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        assert_eq!(basics.make.as_deref(), Some(make));
+        assert_eq!(basics.model.as_deref(), Some(model));
+    }
+
+    #[rstest]
+    #[case(Some("1998:06:14 09:12:30"), "1998-06-14T09:12:30Z")]
+    #[case(None, "2024-01-02T03:04:05Z")]
+    fn has_digitized_date(#[case] digitized: Option<&str>, #[case] expected: &str) {
+        use chrono::DateTime;
+        use little_exif::exif_tag::ExifTag;
+
+        // A scanned film: no DateTimeOriginal, the scan time in
+        // DateTimeDigitized (little_exif CreateDate)
+        let mut metadata = little_exif::metadata::Metadata::new();
+        if let Some(digitized) = digitized {
+            metadata.set_tag(ExifTag::CreateDate(digitized.to_string()));
+        }
+        metadata.set_tag(ExifTag::ModifyDate("2024:01:02 03:04:05".to_string()));
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        let parse = |d: &str| DateTime::parse_from_rfc3339(d).unwrap().to_utc();
+        assert_eq!(basics.original_date, None);
+        assert_eq!(
+            basics.creation_date,
+            digitized.map(|_| parse("1998-06-14T09:12:30Z"))
+        );
+        assert_eq!(basics.best_date(), Some(parse(expected)));
+    }
+
+    #[test]
+    fn has_fields_in_declaration_order() {
+        use crate::DynamicGetSet;
+
+        let basics = Basics {
+            width: Some(1024),
+            make: Some("SONY".to_string()),
+            ..Default::default()
+        };
+        let fields = basics.fields();
+        let names: Vec<&str> = fields.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, Basics::get_field_names());
+        for (name, value) in fields {
+            match name {
+                "width" => assert_eq!(value.unwrap().downcast_ref::<usize>(), Some(&1024)),
+                "make" => assert_eq!(
+                    value.unwrap().downcast_ref::<String>(),
+                    Some(&"SONY".to_string())
+                ),
+                _ => assert!(value.is_none(), "{name} should be unset"),
+            }
+        }
+    }
+
+    #[test]
+    fn has_cleared_field() {
+        use crate::{DynamicGetSet, FieldError};
+
+        let mut basics = Basics {
+            make: Some("SONY".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(basics.clear_field_by_name("make"), Ok(()));
+        assert!(basics.make.is_none());
+        assert_eq!(
+            basics.clear_field_by_name("lens"),
+            Err(FieldError::UnknownField("lens".to_string()))
+        );
+    }
+
+    #[test]
+    fn has_description() {
+        use little_exif::exif_tag::ExifTag;
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::ImageDescription("A car".to_string()));
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        assert_eq!(basics.description.as_deref(), Some("A car"));
+    }
+
+    #[rstest]
+    #[case(
+        "text_car_animal_no-gps.png",
+        Hemisphere::North,
+        Season::Winter,
+        DayPart::Afternoon
+    )]
+    #[case(
+        "text_icon_gps.jpg",
+        Hemisphere::South,
+        Season::Spring,
+        DayPart::Evening
+    )]
+    fn has_season_and_time_of_day(
+        #[case] filename: &str,
+        #[case] hemisphere: Hemisphere,
+        #[case] season: Season,
+        #[case] day_part: DayPart,
+    ) {
+        let mut basics = Basics::default();
+        basics.assign(&get_metadata(filename)).unwrap();
+        assert_eq!(basics.season(hemisphere), Some(season));
+        assert_eq!(basics.time_of_day(), Some(day_part));
+        assert_eq!(Basics::default().season(hemisphere), None);
+        assert_eq!(Basics::default().time_of_day(), None);
+    }
+
+    #[test]
+    fn has_shifted_dates() {
+        use chrono::TimeDelta;
+
+        let date = DateTime::parse_from_rfc3339("2024-10-29T11:30:47Z")
+            .unwrap()
+            .to_utc();
+        let mut basics = Basics {
+            original_date: Some(date),
+            modification_date: Some(date),
+            ..Default::default()
+        };
+        basics.shift(TimeDelta::seconds(-47));
+        let expected = DateTime::parse_from_rfc3339("2024-10-29T11:30:00Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(basics.original_date, Some(expected));
+        assert_eq!(basics.modification_date, Some(expected));
+        assert_eq!(basics.creation_date, None);
+    }
+
+    #[rstest]
+    #[case("text_car_animal_no-gps.png", "2024-12-27T14:58:43.101Z")]
+    #[case("text_icon_gps.jpg", "2024-10-28T19:35:03.383Z")]
+    fn has_utc_date(#[case] filename: &str, #[case] expected: &str) {
+        use chrono::FixedOffset;
+
+        let mut basics = Basics::default();
+        basics.assign(&get_metadata(filename)).unwrap();
+        let offset = FixedOffset::east_opt(3600);
+        assert_eq!(basics.original_offset, offset);
+        assert_eq!(basics.digitized_offset, offset);
+        assert_eq!(basics.modification_offset, offset);
+        let expected = DateTime::parse_from_rfc3339(expected).unwrap().to_utc();
+        assert_eq!(basics.best_utc_date(), Some(expected));
+        assert_eq!(
+            basics.best_local_date(),
+            basics.best_date().map(|d| d.naive_utc())
+        );
+
+        basics.original_offset = None;
+        assert_eq!(basics.best_date_with_offset(), None);
+    }
+
+    #[test]
+    fn has_dimension_sources() {
+        use little_exif::exif_tag::ExifTag;
+
+        // Cropped photo still carrying the camera dimensions
+        let mut exif = little_exif::metadata::Metadata::new();
+        exif.set_tag(ExifTag::ImageWidth(vec![4000]));
+        exif.set_tag(ExifTag::ImageHeight(vec![3000]));
+        let data = std::fs::read(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../resources/img/text_car_animal_no-gps.png"),
+        )
+        .unwrap();
+
+        let mut basics = Basics::default();
+        assert!(basics.assign_dimensions(&exif, &data, &DimensionSource::order(false)));
+        assert_eq!((basics.width, basics.height), (Some(4000), Some(3000)));
+
+        let empty = little_exif::metadata::Metadata::new();
+        let mut basics = Basics::default();
+        assert!(!basics.assign_dimensions(&empty, &data, &[DimensionSource::ExifImageTag]));
+        assert_eq!(basics.width, None);
+
+        #[cfg(feature = "pixels")]
+        {
+            assert!(basics.assign_dimensions(&exif, &data, &DimensionSource::order(true)));
+            assert_eq!((basics.width, basics.height), (Some(1024), Some(769)));
+        }
+    }
 }
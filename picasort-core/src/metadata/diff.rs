@@ -0,0 +1,227 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::any::Any;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Utc};
+
+use crate::DynamicGetSet;
+use crate::metadata::Metadata;
+use crate::values::{ExposureProgram, GPSCoord, Orientation, SceneCaptureType, WhiteBalance};
+
+/// A field whose value differs between two `Metadata`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Struct and field names, e.g. "basics.software"
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Lists the fields of basics, gps and exposure, then the XMP flags, that
+/// differ from `a` to `b`, in declaration order. The file path and content
+/// hash are not compared.
+pub fn diff(a: &Metadata, b: &Metadata) -> Vec<FieldDiff> {
+    let mut diffs = Vec::new();
+    diff_fields("basics", &a.basics, &b.basics, &mut diffs);
+    diff_fields("gps", &a.gps, &b.gps, &mut diffs);
+    diff_fields("exposure", &a.exposure, &b.exposure, &mut diffs);
+    if a.gpano != b.gpano {
+        diffs.push(FieldDiff {
+            field: "gpano".to_string(),
+            old: Some(a.gpano.to_string()),
+            new: Some(b.gpano.to_string()),
+        });
+    }
+    diffs
+}
+
+fn diff_fields<T: DynamicGetSet>(prefix: &str, a: &T, b: &T, diffs: &mut Vec<FieldDiff>) {
+    for ((name, old), (_, new)) in a.fields().into_iter().zip(b.fields()) {
+        let old = old.map(|v| value_to_string(name, v));
+        let new = new.map(|v| value_to_string(name, v));
+        if old != new {
+            diffs.push(FieldDiff {
+                field: format!("{prefix}.{name}"),
+                old,
+                new,
+            });
+        }
+    }
+}
+
+/// Rendering of a field value of a type unknown to `value_to_string`
+pub const UNRENDERABLE: &str = "<unrenderable>";
+
+/// Renders the value of a field, for the types found in the metadata structs.
+/// Another type gives `UNRENDERABLE`, so that its changes are not seen, and
+/// fails a debug assertion for the tests to catch it.
+fn value_to_string(field: &str, value: &dyn Any) -> String {
+    if let Some(v) = value.downcast_ref::<String>() {
+        v.clone()
+    } else if let Some(v) = value.downcast_ref::<bool>() {
+        v.to_string()
+    } else if let Some(v) = value.downcast_ref::<usize>() {
+        v.to_string()
+    } else if let Some(v) = value.downcast_ref::<f64>() {
+        v.to_string()
+    } else if let Some(v) = value.downcast_ref::<DateTime<Utc>>() {
+        v.to_rfc3339()
+    } else if let Some(v) = value.downcast_ref::<FixedOffset>() {
+        v.to_string()
+    } else if let Some(v) = value.downcast_ref::<NaiveDate>() {
+        v.to_string()
+    } else if let Some(v) = value.downcast_ref::<NaiveTime>() {
+        v.to_string()
+    } else if let Some(v) = value.downcast_ref::<Orientation>() {
+        format!("{v:?}")
+    } else if let Some(v) = value.downcast_ref::<ExposureProgram>() {
+        format!("{v:?}")
+    } else if let Some(v) = value.downcast_ref::<SceneCaptureType>() {
+        format!("{v:?}")
+    } else if let Some(v) = value.downcast_ref::<WhiteBalance>() {
+        format!("{v:?}")
+    } else if let Some(v) = value.downcast_ref::<GPSCoord>() {
+        format!("{}°{}'{}\"", v.deg, v.min, v.sec)
+    } else if let Some(v) = value.downcast_ref::<Vec<usize>>() {
+        format!("{v:?}")
+    } else {
+        debug_assert!(false, "diff: no rendering of the type of the field {field}");
+        UNRENDERABLE.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::{basics::Basics, exposure::Exposure, gps::GPSData};
+    use std::path::Path;
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "no rendering"))]
+    fn has_unrenderable_value() {
+        assert_eq!(value_to_string("raw", &1u8), UNRENDERABLE);
+    }
+
+    #[test]
+    fn has_diff() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        let original = Metadata::from_path(&path).unwrap();
+        assert!(diff(&original, &original).is_empty());
+
+        let mut edited = Metadata::from_path(&path).unwrap();
+        edited.basics.software = Some("GIMP 2.10.38".to_string());
+        edited.gps.latitude = None;
+        edited.exposure.iso = Some(100);
+        assert_eq!(
+            diff(&original, &edited),
+            vec![
+                FieldDiff {
+                    field: "basics.software".to_string(),
+                    old: Some("18.0.1".to_string()),
+                    new: Some("GIMP 2.10.38".to_string()),
+                },
+                FieldDiff {
+                    field: "gps.latitude".to_string(),
+                    old: Some("45°45'37.05\"".to_string()),
+                    new: None,
+                },
+                FieldDiff {
+                    field: "exposure.iso".to_string(),
+                    old: Some("1250".to_string()),
+                    new: Some("100".to_string()),
+                },
+            ]
+        );
+    }
+
+    /// Every field set, without `..Default::default()` so that a new field
+    /// has to be added here and its type rendered by `value_to_string`
+    #[test]
+    fn has_diff_of_every_field() {
+        let date = DateTime::parse_from_rfc3339("2024-10-28T20:35:03Z")
+            .unwrap()
+            .to_utc();
+        let offset = FixedOffset::east_opt(3600);
+        let text = || Some("text".to_string());
+        let coord = Some(GPSCoord {
+            deg: 45,
+            min: 45,
+            sec: 37.05,
+        });
+        let full = Metadata {
+            file_path: Default::default(),
+            uuid: String::new(),
+            basics: Basics {
+                width: Some(1),
+                height: Some(1),
+                description: text(),
+                resolution_x: Some(1),
+                resolution_y: Some(1),
+                resolution_unit: Some(1),
+                orientation: Some(Orientation::Normal),
+                creation_date: Some(date),
+                original_date: Some(date),
+                modification_date: Some(date),
+                original_offset: offset,
+                digitized_offset: offset,
+                modification_offset: offset,
+                copyright: text(),
+                artist: text(),
+                software: text(),
+                processing_software: text(),
+                image_unique_id: text(),
+                make: text(),
+                model: text(),
+            },
+            gps: GPSData {
+                latitude_ref: text(),
+                latitude: coord.clone(),
+                longitude_ref: text(),
+                longitude: coord,
+                time: Some(date.time()),
+                date: Some(date.date_naive()),
+                altitude: Some(1.0),
+                processing_method: text(),
+                map_datum: text(),
+                dop: Some(1.0),
+                h_positioning_error: Some(1.0),
+                differential: Some(false),
+                area_information: text(),
+                city: text(),
+                country: text(),
+            },
+            exposure: Exposure {
+                exposure_time: Some(1.0),
+                f_number: Some(1.0),
+                iso: Some(1),
+                focal_length: Some(1.0),
+                focal_length_35mm: Some(1),
+                subject_distance: Some(1.0),
+                subject_area: Some(vec![1, 1]),
+                scene_capture_type: Some(SceneCaptureType::Standard),
+                white_balance: Some(WhiteBalance::Auto),
+                exposure_program: Some(ExposureProgram::Normal),
+                digital_zoom_ratio: Some(1.0),
+            },
+            gpano: true,
+        };
+        let count = Basics::get_field_names().len()
+            + GPSData::get_field_names().len()
+            + Exposure::get_field_names().len()
+            + 1;
+        assert_eq!(diff(&Metadata::default(), &full).len(), count);
+        assert!(diff(&full, &full).is_empty());
+
+        let mut corrected = full.clone();
+        corrected.gps.differential = Some(true);
+        assert_eq!(
+            diff(&full, &corrected),
+            vec![FieldDiff {
+                field: "gps.differential".to_string(),
+                old: Some("false".to_string()),
+                new: Some("true".to_string()),
+            }]
+        );
+    }
+}
@@ -1,42 +1,103 @@
 // Copyright (c) 2024 Lemur-Catta.org
 // Author: Sylvain Gubian <sgubian@lemur-catta.org>
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use crate::{
     DynamicGetSet,
     error::CoreError,
     metadata::{basics::Orientation, gps::GPSCoord},
+    utils::sha::hash_bytes,
+    values::{ExposureProgram, Rational, SceneCaptureType, WhiteBalance},
 };
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 use little_exif::{
-    exif_tag::ExifTag, metadata::Metadata, rational::uR64, u8conversion::U8conversion,
+    endian::Endian, exif_tag::ExifTag, metadata::Metadata, rational::uR64,
+    u8conversion::U8conversion,
 };
 
+impl From<uR64> for Rational {
+    fn from(value: uR64) -> Self {
+        Rational {
+            num: value.nominator,
+            den: value.denominator,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ExtractedValue {
     Text(String),
-    Numbers(Vec<uR64>),
+    Numbers(Vec<Rational>),
     UnsignedInt(usize),
+    UnsignedInts(Vec<usize>),
+    Float(f64),
     Date(NaiveDate),
     Time(NaiveTime),
     GPSCoord(GPSCoord),
     Orientation(Orientation),
+    ExposureProgram(ExposureProgram),
+    SceneCaptureType(SceneCaptureType),
+    WhiteBalance(WhiteBalance),
     DateTime(DateTime<Utc>),
+    Offset(FixedOffset),
+    Flag(bool),
     // add more as needed
 }
 
+pub type Converter = fn(&ExifTag, &Metadata) -> Option<ExtractedValue>;
+
 pub struct TagContext<'a> {
     pub destination: &'a str,
     pub main_tag: ExifTag,
     pub alternative: Option<ExifTag>,
-    pub convert: fn(&ExifTag, &Metadata) -> Option<ExtractedValue>,
+    pub convert: Converter,
+}
+
+/// Converters registered at runtime by destination field name. They replace
+/// the curated converter of the matching `TagContext` in `assign_with_registry`.
+#[derive(Debug, Default, Clone)]
+pub struct ConverterRegistry {
+    converters: HashMap<String, Converter>,
+}
+
+impl ConverterRegistry {
+    pub fn new() -> ConverterRegistry {
+        ConverterRegistry::default()
+    }
+
+    /// Registers `convert` for `destination`, returning the replaced converter
+    pub fn register(&mut self, destination: &str, convert: Converter) -> Option<Converter> {
+        self.converters.insert(destination.to_string(), convert)
+    }
+
+    pub fn get(&self, destination: &str) -> Option<Converter> {
+        self.converters.get(destination).copied()
+    }
+}
+
+/// Structs whose fields can be written back to a file as EXIF tags
+pub trait ExifWritable {
+    /// Tags of the fields holding a value
+    fn exif_tags(&self) -> Vec<ExifTag>;
 }
 
 pub struct ExtractionSet<'a> {
     pub tags: Vec<TagContext<'a>>,
 }
 
+impl<'a> ExtractionSet<'a> {
+    /// Tags of both sets, those of `other` replacing the ones of `self` with
+    /// the same destination
+    pub fn merge(mut self, other: ExtractionSet<'a>) -> ExtractionSet<'a> {
+        self.tags
+            .retain(|tag| !other.tags.iter().any(|o| o.destination == tag.destination));
+        self.tags.extend(other.tags);
+        self
+    }
+}
+
 pub trait ExifExtractable {
     type Output;
     fn extract(exif_tag: &ExifTag, metadata: &Metadata) -> Self::Output;
@@ -50,13 +111,21 @@ pub trait ExifAssignable<'a>: DynamicGetSet + Debug {
         true
     }
     fn assign(&mut self, metadata: &Metadata) -> Result<(), &'static str> {
+        self.assign_with_registry(metadata, &ConverterRegistry::default())
+    }
+    fn assign_with_registry(
+        &mut self,
+        metadata: &Metadata,
+        registry: &ConverterRegistry,
+    ) -> Result<(), &'static str> {
         if let Some(es) = self.exif_set() {
             for tag in es.tags {
-                let mut value = (tag.convert)(&tag.main_tag, metadata);
+                let convert = registry.get(tag.destination).unwrap_or(tag.convert);
+                let mut value = convert(&tag.main_tag, metadata);
                 if value.is_none()
                     && let Some(alt_tag) = tag.alternative
                 {
-                    value = (tag.convert)(&alt_tag, metadata);
+                    value = convert(&alt_tag, metadata);
                 }
 
                 match value {
@@ -75,15 +144,36 @@ pub trait ExifAssignable<'a>: DynamicGetSet + Debug {
                     Some(ExtractedValue::UnsignedInt(i)) => {
                         self.set_field_by_name(tag.destination, Box::new(Some(i)))?;
                     }
+                    Some(ExtractedValue::UnsignedInts(v)) => {
+                        self.set_field_by_name(tag.destination, Box::new(Some(v)))?;
+                    }
+                    Some(ExtractedValue::Float(f)) => {
+                        self.set_field_by_name(tag.destination, Box::new(Some(f)))?;
+                    }
                     Some(ExtractedValue::GPSCoord(c)) => {
                         self.set_field_by_name(tag.destination, Box::new(Some(c)))?;
                     }
                     Some(ExtractedValue::Orientation(o)) => {
                         self.set_field_by_name(tag.destination, Box::new(Some(o)))?;
                     }
+                    Some(ExtractedValue::ExposureProgram(p)) => {
+                        self.set_field_by_name(tag.destination, Box::new(Some(p)))?;
+                    }
+                    Some(ExtractedValue::SceneCaptureType(s)) => {
+                        self.set_field_by_name(tag.destination, Box::new(Some(s)))?;
+                    }
+                    Some(ExtractedValue::WhiteBalance(w)) => {
+                        self.set_field_by_name(tag.destination, Box::new(Some(w)))?;
+                    }
                     Some(ExtractedValue::DateTime(dt)) => {
                         self.set_field_by_name(tag.destination, Box::new(Some(dt)))?;
                     }
+                    Some(ExtractedValue::Offset(o)) => {
+                        self.set_field_by_name(tag.destination, Box::new(Some(o)))?;
+                    }
+                    Some(ExtractedValue::Flag(f)) => {
+                        self.set_field_by_name(tag.destination, Box::new(Some(f)))?;
+                    }
                     None => (),
                 }
             }
@@ -95,8 +185,9 @@ pub trait ExifAssignable<'a>: DynamicGetSet + Debug {
 fn get_tag_value<T: U8conversion<T>>(tag: &ExifTag, metadata: &Metadata) -> Result<T, CoreError> {
     if let Some(tag) = metadata.get_tag(tag).next() {
         let endian = metadata.get_endian();
-        let tag_value = <T>::from_u8_vec(&tag.value_as_u8_vec(&endian), &endian);
-        return Ok(tag_value);
+        // from_u8_vec panics on mangled data, the fallible version is used instead
+        return <T>::from_u8_vec_res(&tag.value_as_u8_vec(&endian), &endian)
+            .map_err(|e| CoreError::InvalidEXIFConversion(e.to_string()));
     }
     Err(CoreError::EXIFTagNotFound())
 }
@@ -107,6 +198,48 @@ pub fn extract_unsigned_int32(tag: &ExifTag, meta: &Metadata) -> Option<Extracte
     Some(ExtractedValue::UnsignedInt(value as usize))
 }
 
+pub fn extract_rounded_rational(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<uR64>::extract(tag, meta)?;
+    let value = v.first()?;
+    if value.denominator == 0 {
+        return None;
+    }
+    let value = (value.nominator as f64 / value.denominator as f64).round();
+    Some(ExtractedValue::UnsignedInt(value as usize))
+}
+
+pub fn extract_rational(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<uR64>::extract(tag, meta)?;
+    let value = v.first()?;
+    if value.denominator == 0 {
+        return None;
+    }
+    Some(ExtractedValue::Float(
+        value.nominator as f64 / value.denominator as f64,
+    ))
+}
+
+/// Subject distance in meters: a 0 nominator means unknown and 0xFFFFFFFF infinity
+pub fn extract_subject_distance(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<uR64>::extract(tag, meta)?;
+    let value = v.first()?;
+    match (value.nominator, value.denominator) {
+        (0, _) | (_, 0) => None,
+        (u32::MAX, _) => Some(ExtractedValue::Float(f64::INFINITY)),
+        (n, d) => Some(ExtractedValue::Float(n as f64 / d as f64)),
+    }
+}
+
+pub fn extract_unsigned_int16_vec(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<u16>::extract(tag, meta)?;
+    if v.is_empty() {
+        return None;
+    }
+    Some(ExtractedValue::UnsignedInts(
+        v.into_iter().map(|i| i as usize).collect(),
+    ))
+}
+
 pub fn extract_orientation(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
     let v = Vec::<u16>::extract(tag, meta)?;
     Some(ExtractedValue::Orientation(Orientation::from_code(
@@ -114,25 +247,180 @@ pub fn extract_orientation(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedVa
     )))
 }
 
+pub fn extract_exposure_program(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<u16>::extract(tag, meta)?;
+    Some(ExtractedValue::ExposureProgram(ExposureProgram::from_code(
+        *v.first()?,
+    )))
+}
+
+/// Digital zoom ratio, a 0 nominator meaning no digital zoom is read as 1
+pub fn extract_digital_zoom_ratio(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<uR64>::extract(tag, meta)?;
+    let value = v.first()?;
+    match (value.nominator, value.denominator) {
+        (_, 0) => None,
+        (0, _) => Some(ExtractedValue::Float(1.0)),
+        (n, d) => Some(ExtractedValue::Float(n as f64 / d as f64)),
+    }
+}
+
+/// 35 mm equivalent focal length, None for the 0 meaning unknown
+pub fn extract_focal_length_35mm(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<u16>::extract(tag, meta)?;
+    let focal_length = *v.first()?;
+    (focal_length != 0).then_some(ExtractedValue::UnsignedInt(focal_length as usize))
+}
+
+pub fn extract_scene_capture_type(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<u16>::extract(tag, meta)?;
+    Some(ExtractedValue::SceneCaptureType(
+        SceneCaptureType::from_code(*v.first()?),
+    ))
+}
+
+pub fn extract_white_balance(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<u16>::extract(tag, meta)?;
+    Some(ExtractedValue::WhiteBalance(WhiteBalance::from_code(
+        *v.first()?,
+    )))
+}
+
 pub fn extract_unsigned_int16(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
     let v = Vec::<u16>::extract(tag, meta)?;
     Some(ExtractedValue::UnsignedInt(*v.first()? as usize))
 }
 
+/// Flag of a short tag, 0 for false and 1 for true, None for other values
+pub fn extract_flag(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<u16>::extract(tag, meta)?;
+    match v.first()? {
+        0 => Some(ExtractedValue::Flag(false)),
+        1 => Some(ExtractedValue::Flag(true)),
+        _ => None,
+    }
+}
+
 pub fn extract_string(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
     String::extract(tag, meta).map(ExtractedValue::Text)
 }
 
+/// Character code prefixes of UNDEFINED text tags (UserComment,
+/// GPSProcessingMethod, GPSAreaInformation)
+const ASCII_PREFIX: &[u8; 8] = b"ASCII\0\0\0";
+const UNICODE_PREFIX: &[u8; 8] = b"UNICODE\0";
+const JIS_PREFIX: &[u8; 8] = b"JIS\0\0\0\0\0";
+const UNDEFINED_PREFIX: &[u8; 8] = &[0; 8];
+
+/// Text of an UNDEFINED text tag (UserComment, GPSProcessingMethod,
+/// GPSAreaInformation) from its 8-byte character code prefix: UTF-16 for
+/// `UNICODE`, else ASCII, read as UTF-8 as most writers do. Bytes without a
+/// known prefix are taken as text. The UTF-16 byte order is the one of its
+/// byte order mark, else the one putting the zero bytes of Latin text in the
+/// high byte, else little endian. The text ends at its first null
+/// character, trailing spaces are dropped. None when the text is empty.
+pub fn decode_prefixed_string(bytes: &[u8]) -> Option<String> {
+    let text = match bytes.first_chunk::<8>() {
+        Some(UNICODE_PREFIX) => decode_utf16(&bytes[8..]),
+        Some(ASCII_PREFIX | JIS_PREFIX | UNDEFINED_PREFIX) => decode_ascii(&bytes[8..]),
+        _ => decode_ascii(bytes),
+    };
+    let text = text.trim_end();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn decode_ascii(payload: &[u8]) -> String {
+    let end = payload
+        .iter()
+        .position(|b| *b == 0)
+        .unwrap_or(payload.len());
+    String::from_utf8_lossy(&payload[..end]).into_owned()
+}
+
+fn decode_utf16(payload: &[u8]) -> String {
+    let (payload, big_endian) = match payload {
+        [0xFE, 0xFF, rest @ ..] => (rest, true),
+        [0xFF, 0xFE, rest @ ..] => (rest, false),
+        _ => {
+            let zeros = |start: usize| {
+                payload
+                    .iter()
+                    .skip(start)
+                    .step_by(2)
+                    .filter(|b| **b == 0)
+                    .count()
+            };
+            (payload, zeros(0) > zeros(1))
+        }
+    };
+    decode_utf16_with(payload, big_endian)
+}
+
+/// UTF-16 text of a known byte order, up to the first NUL
+pub(crate) fn decode_utf16_with(payload: &[u8], big_endian: bool) -> String {
+    let units: Vec<u16> = payload
+        .chunks_exact(2)
+        .map(|c| match big_endian {
+            true => u16::from_be_bytes([c[0], c[1]]),
+            false => u16::from_le_bytes([c[0], c[1]]),
+        })
+        .take_while(|u| *u != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// String of an UNDEFINED text tag, see `decode_prefixed_string`
+pub fn extract_prefixed_string(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    decode_prefixed_string(&Vec::<u8>::extract(tag, meta)?).map(ExtractedValue::Text)
+}
+
 pub fn extract_numbers(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
-    Vec::<uR64>::extract(tag, meta).map(ExtractedValue::Numbers)
+    let v = Vec::<uR64>::extract(tag, meta)?;
+    Some(ExtractedValue::Numbers(
+        v.into_iter().map(Rational::from).collect(),
+    ))
 }
 
 pub fn extract_naive_date(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
     NaiveDate::extract(tag, meta).map(ExtractedValue::Date)
 }
 
+/// Date time with the sub-seconds of the matching SubSecTime tag when present.
+///
+/// EXIF date times are the local time of the camera without timezone: the
+/// result is that local time labelled as UTC, not the actual UTC time. The
+/// offset is in the OffsetTime tags, see `extract_offset`.
 pub fn extract_utc_datetime(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
-    DateTime::<Utc>::extract(tag, meta).map(ExtractedValue::DateTime)
+    let datetime = DateTime::<Utc>::extract(tag, meta)?;
+    let subsec_tag = match tag {
+        ExifTag::DateTimeOriginal(_) => ExifTag::SubSecTimeOriginal(String::new()),
+        ExifTag::CreateDate(_) => ExifTag::SubSecTimeDigitized(String::new()),
+        ExifTag::ModifyDate(_) => ExifTag::SubSecTime(String::new()),
+        _ => return Some(ExtractedValue::DateTime(datetime)),
+    };
+    let nanos = String::extract(&subsec_tag, meta).and_then(|s| subsec_nanos(&s));
+    match nanos.and_then(|n| datetime.with_nanosecond(n)) {
+        Some(precise) => Some(ExtractedValue::DateTime(precise)),
+        None => Some(ExtractedValue::DateTime(datetime)),
+    }
+}
+
+/// Converts the digits of a SubSecTime tag (a decimal fraction, "38" is 0.38s)
+/// to nanoseconds
+fn subsec_nanos(subsec: &str) -> Option<u32> {
+    let digits = subsec.trim();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let digits = &digits[..digits.len().min(9)];
+    let value: u32 = digits.parse().ok()?;
+    Some(value * 10u32.pow(9 - digits.len() as u32))
+}
+
+/// Offset from UTC of an OffsetTime tag, e.g. "+01:00"
+pub fn extract_offset(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let offset = String::extract(tag, meta)?;
+    offset.trim().parse().ok().map(ExtractedValue::Offset)
 }
 
 pub fn extract_naive_time(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
@@ -141,18 +429,43 @@ pub fn extract_naive_time(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedVal
 
 pub fn extract_gps_coord(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
     if let Some(v) = Vec::<uR64>::extract(tag, meta) {
-        let mut coord = GPSCoord::default();
-        if v.len() != 3 {
+        let [deg, min, sec] = v.as_slice() else {
+            return None;
+        };
+        if sec.denominator == 0 {
             return None;
         }
-        coord.deg = v[0].nominator as usize;
-        coord.min = v[1].nominator as usize;
-        coord.sec = v[2].nominator as f64 / v[2].denominator as f64;
+        let coord = GPSCoord {
+            deg: deg.nominator as usize,
+            min: min.nominator as usize,
+            sec: sec.nominator as f64 / sec.denominator as f64,
+        };
         return Some(ExtractedValue::GPSCoord(coord));
     }
     None
 }
 
+/// Signed altitude in meters, negative when GPSAltitudeRef says below sea level.
+/// The reference is expected as a byte but some writers use a short, so any
+/// non-zero byte is considered as below sea level.
+pub fn extract_gps_altitude(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+    let v = Vec::<uR64>::extract(tag, meta)?;
+    let value = v.first()?;
+    if value.denominator == 0 {
+        return None;
+    }
+    let mut altitude = value.nominator as f64 / value.denominator as f64;
+    if let Some(reference) = meta.get_tag(&ExifTag::GPSAltitudeRef(Vec::new())).next()
+        && reference
+            .value_as_u8_vec(&meta.get_endian())
+            .iter()
+            .any(|b| *b != 0)
+    {
+        altitude = -altitude;
+    }
+    Some(ExtractedValue::Float(altitude))
+}
+
 impl ExifExtractable for String {
     type Output = Option<String>;
     fn extract(exif_tag: &ExifTag, metadata: &Metadata) -> Self::Output {
@@ -181,7 +494,7 @@ impl ExifExtractable for NaiveDate {
     type Output = Option<NaiveDate>;
     fn extract(exif_tag: &ExifTag, metadata: &Metadata) -> Self::Output {
         let date_str = String::extract(exif_tag, metadata)?;
-        Some(NaiveDate::parse_from_str(&date_str, "%Y:%m:%d").unwrap_or_default())
+        NaiveDate::parse_from_str(&date_str, "%Y:%m:%d").ok()
     }
 }
 
@@ -200,13 +513,296 @@ impl ExifExtractable for NaiveTime {
     type Output = Option<NaiveTime>;
     fn extract(exif_tag: &ExifTag, metadata: &Metadata) -> Self::Output {
         let time_u64_vec = <Vec<uR64>>::extract(exif_tag, metadata)?;
-        let time_str = format!(
-            "{:?}:{:?}:{:?}",
-            time_u64_vec[0].nominator, time_u64_vec[1].nominator, time_u64_vec[2].nominator
-        );
-        let Ok(nt) = NaiveTime::parse_from_str(&time_str, "%H:%M:%S") else {
+        let [hour, min, sec] = time_u64_vec.as_slice() else {
             return None;
         };
-        Some(nt)
+        let part = |r: &uR64| r.nominator.checked_div(r.denominator);
+        NaiveTime::from_hms_opt(part(hour)?, part(min)?, part(sec)?)
+    }
+}
+
+/// Hex SHA-256 of the EXIF tags, ignoring the pixel data. The tags are
+/// serialized big endian and sorted by group, IFD number and tag id; the
+/// offsets to sub IFDs are left out. The hash is then stable across re-saves
+/// keeping the same tags.
+pub fn exif_hash(metadata: &Metadata) -> String {
+    let mut entries: Vec<(String, u32, u16, Vec<u8>)> = Vec::new();
+    for ifd in metadata.get_ifds() {
+        let group = format!("{:?}", ifd.get_ifd_type());
+        for tag in ifd.get_tags().iter().filter(|t| t.is_writable()) {
+            entries.push((
+                group.clone(),
+                ifd.get_generic_ifd_nr(),
+                tag.as_u16(),
+                tag.value_as_u8_vec(&Endian::Big),
+            ));
+        }
+    }
+    entries.sort();
+    let mut canonical = Vec::new();
+    for (group, ifd_nr, tag, value) in entries {
+        canonical.extend_from_slice(group.as_bytes());
+        canonical.extend_from_slice(&ifd_nr.to_be_bytes());
+        canonical.extend_from_slice(&tag.to_be_bytes());
+        canonical.extend_from_slice(&(value.len() as u64).to_be_bytes());
+        canonical.extend_from_slice(&value);
+    }
+    hash_bytes(&canonical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use little_exif::ifd::ExifTagGroup;
+    use rstest::rstest;
+
+    fn rationals(values: &[(u32, u32)]) -> Vec<uR64> {
+        values
+            .iter()
+            .map(|(nominator, denominator)| uR64 {
+                nominator: *nominator,
+                denominator: *denominator,
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(b"ASCII\0\0\0Lyon", Some("Lyon"))]
+    #[case(b"JIS\0\0\0\0\0Tokyo\0\0", Some("Tokyo"))]
+    #[case(b"\0\0\0\0\0\0\0\0Caf\xC3\xA9  ", Some("Café"))]
+    #[case(b"UNICODE\0C\0a\0f\0\xE9\0\0\0", Some("Café"))]
+    #[case(b"UNICODE\0\0C\0a\0f\0\xE9", Some("Café"))]
+    #[case(b"UNICODE\0\xFE\xFF\x65\xE5\x67\x2C", Some("日本"))]
+    #[case(b"UNICODE\0\xFF\xFE\xE5\x65\x2C\x67", Some("日本"))]
+    #[case(b"WLAN\0", Some("WLAN"))]
+    #[case(b"ASCII\0\0\0", None)]
+    #[case(b"UNICODE\0\0\0", None)]
+    #[case(b"\0\0\0\0\0\0\0\0        ", None)]
+    #[case(b"", None)]
+    fn has_decoded_prefixed_string(#[case] bytes: &[u8], #[case] expected: Option<&str>) {
+        assert_eq!(decode_prefixed_string(bytes).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(&[], false)]
+    #[case(&[(11, 1)], false)]
+    #[case(&[(11, 1), (33, 1)], false)]
+    #[case(&[(11, 1), (33, 1), (25, 0)], false)]
+    #[case(&[(11, 1), (33, 1), (99, 1)], false)]
+    #[case(&[(11, 1), (33, 1), (25, 1), (0, 1)], false)]
+    #[case(&[(11, 1), (33, 1), (2540, 100)], true)]
+    fn has_total_time_extraction(#[case] values: &[(u32, u32)], #[case] expected: bool) {
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::GPSTimeStamp(rationals(values)));
+        let time = NaiveTime::extract(&ExifTag::GPSTimeStamp(Vec::new()), &metadata);
+        assert_eq!(time.is_some(), expected);
+    }
+
+    #[rstest]
+    #[case(&[], false)]
+    #[case(&[(45, 1), (45, 1)], false)]
+    #[case(&[(45, 1), (45, 1), (3705, 0)], false)]
+    #[case(&[(45, 1), (45, 1), (3705, 100)], true)]
+    fn has_total_gps_coord_extraction(#[case] values: &[(u32, u32)], #[case] expected: bool) {
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::GPSLatitude(rationals(values)));
+        let coord = extract_gps_coord(&ExifTag::GPSLatitude(Vec::new()), &metadata);
+        assert_eq!(coord.is_some(), expected);
+    }
+
+    #[rstest]
+    #[case(&[], false)]
+    #[case(&[6], false)]
+    #[case(&[6, 0, 1], false)]
+    #[case(&[6, 0], true)]
+    fn has_total_orientation_extraction(#[case] raw: &[u8], #[case] expected: bool) {
+        // Orientation is a short: an empty or odd sized payload is mangled
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::UnknownINT8U(
+            raw.to_vec(),
+            0x0112,
+            ExifTagGroup::GENERIC,
+        ));
+        let orientation = extract_orientation(&ExifTag::Orientation(Vec::new()), &metadata);
+        assert_eq!(orientation.is_some(), expected);
+    }
+
+    #[test]
+    fn has_registry_converter() {
+        use crate::metadata::basics::Basics;
+
+        fn upper_software(tag: &ExifTag, meta: &Metadata) -> Option<ExtractedValue> {
+            String::extract(tag, meta).map(|s| ExtractedValue::Text(s.to_uppercase()))
+        }
+
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::Software("gimp".to_string()));
+        let mut registry = ConverterRegistry::new();
+        assert!(registry.register("software", upper_software).is_none());
+
+        let mut basics = Basics::default();
+        basics.assign_with_registry(&metadata, &registry).unwrap();
+        assert_eq!(basics.software.as_deref(), Some("GIMP"));
+
+        let mut basics = Basics::default();
+        basics.assign(&metadata).unwrap();
+        assert_eq!(basics.software.as_deref(), Some("gimp"));
+    }
+
+    #[rstest]
+    #[case(Some("101"), "2024-12-27T15:58:43.101Z")]
+    #[case(Some("38"), "2024-12-27T15:58:43.380Z")]
+    #[case(Some("1234567891"), "2024-12-27T15:58:43.123456789Z")]
+    #[case(Some(" 5 "), "2024-12-27T15:58:43.500Z")]
+    #[case(Some("abc"), "2024-12-27T15:58:43Z")]
+    #[case(None, "2024-12-27T15:58:43Z")]
+    fn has_subsec_datetime(#[case] subsec: Option<&str>, #[case] expected: &str) {
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::DateTimeOriginal("2024:12:27 15:58:43".to_string()));
+        if let Some(subsec) = subsec {
+            metadata.set_tag(ExifTag::SubSecTimeOriginal(subsec.to_string()));
+        }
+        let Some(ExtractedValue::DateTime(dt)) =
+            extract_utc_datetime(&ExifTag::DateTimeOriginal(String::new()), &metadata)
+        else {
+            panic!("No date time extracted");
+        };
+        assert_eq!(dt, DateTime::parse_from_rfc3339(expected).unwrap().to_utc());
+    }
+
+    #[test]
+    fn has_total_date_extraction() {
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::GPSDateStamp("not a date".to_string()));
+        let date = NaiveDate::extract(&ExifTag::GPSDateStamp(String::new()), &metadata);
+        assert_eq!(date, None);
+    }
+
+    #[test]
+    fn has_numbers_as_rationals() {
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::ExposureTime(rationals(&[(1, 160)])));
+        let value = extract_numbers(&ExifTag::ExposureTime(Vec::new()), &metadata);
+        let Some(ExtractedValue::Numbers(numbers)) = value else {
+            panic!("Expected numbers, got {value:?}");
+        };
+        assert_eq!(numbers, vec![Rational { num: 1, den: 160 }]);
+    }
+
+    #[test]
+    fn has_extraction_set_merge() {
+        use crate::metadata::tags;
+
+        let camera = ExtractionSet {
+            tags: vec![tags::CAMERA_MAKE, tags::CAMERA_MODEL],
+        };
+        let merged = camera
+            .merge(ExtractionSet {
+                tags: vec![tags::DATE_ORIGINAL],
+            })
+            .merge(ExtractionSet {
+                tags: vec![TagContext {
+                    destination: "make",
+                    main_tag: ExifTag::Software(String::new()),
+                    alternative: None,
+                    convert: extract_string,
+                }],
+            });
+        let destinations: Vec<&str> = merged.tags.iter().map(|t| t.destination).collect();
+        assert_eq!(destinations, vec!["model", "original_date", "make"]);
+        assert_eq!(
+            merged.tags[2].main_tag.as_u16(),
+            ExifTag::Software(String::new()).as_u16()
+        );
+    }
+
+    #[test]
+    fn has_non_optional_destination() {
+        #[derive(Debug, Default, DynamicGetSet)]
+        struct Descriptor {
+            width_raw: usize,
+            software: Option<String>,
+        }
+
+        impl<'a> ExifAssignable<'a> for Descriptor {
+            fn exif_set(&self) -> Option<ExtractionSet<'a>> {
+                Some(ExtractionSet {
+                    tags: vec![
+                        TagContext {
+                            destination: "width_raw",
+                            main_tag: ExifTag::ImageWidth(Vec::new()),
+                            alternative: None,
+                            convert: extract_unsigned_int32,
+                        },
+                        TagContext {
+                            destination: "software",
+                            main_tag: ExifTag::Software(String::new()),
+                            alternative: None,
+                            convert: extract_string,
+                        },
+                    ],
+                })
+            }
+        }
+
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::ImageWidth(vec![640]));
+        metadata.set_tag(ExifTag::Software("gimp".to_string()));
+        let mut descriptor = Descriptor::default();
+        descriptor.assign(&metadata).unwrap();
+        assert_eq!(descriptor.width_raw, 640);
+        assert_eq!(descriptor.software.as_deref(), Some("gimp"));
+
+        assert!(
+            descriptor
+                .set_field_by_name("width_raw", Box::new(12usize))
+                .is_ok()
+        );
+        assert_eq!(descriptor.width_raw, 12);
+        assert!(
+            descriptor
+                .set_field_by_name("width_raw", Box::new(None::<usize>))
+                .is_err()
+        );
+        assert!(
+            descriptor
+                .set_field_by_name("width_raw", Box::new(12u32))
+                .is_err()
+        );
+        assert!(
+            descriptor
+                .set_field_by_index(0, Box::new(Some(7usize)))
+                .is_ok()
+        );
+        assert_eq!(descriptor.width_raw, 7);
+        // Derived without `FieldError` in scope
+        assert_eq!(
+            descriptor.clear_field_by_name("width_raw"),
+            Err(crate::FieldError::NotOptional("width_raw"))
+        );
+        assert_eq!(descriptor.clear_field_by_name("software"), Ok(()));
+    }
+
+    #[test]
+    fn has_exif_hash() {
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img");
+        let root = TempDir::new();
+        let path = root.join("photo.jpg");
+        std::fs::copy(fixtures.join("text_icon_gps.jpg"), &path).unwrap();
+        let metadata = Metadata::new_from_path(&path).unwrap();
+        let hash = exif_hash(&metadata);
+        assert_eq!(hash.len(), 64);
+
+        metadata.write_to_file(&path).unwrap();
+        assert_eq!(exif_hash(&Metadata::new_from_path(&path).unwrap()), hash);
+
+        let mut edited = Metadata::new_from_path(&path).unwrap();
+        edited.set_tag(ExifTag::Software("picasort".to_string()));
+        assert_ne!(exif_hash(&edited), hash);
+
+        let png = Metadata::new_from_path(&fixtures.join("text_car_animal_no-gps.png")).unwrap();
+        assert_ne!(exif_hash(&png), hash);
+        assert_eq!(exif_hash(&Metadata::new()), exif_hash(&Metadata::new()));
     }
 }
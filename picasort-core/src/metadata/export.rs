@@ -0,0 +1,179 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use crate::metadata::{Metadata, gps::NULL_ISLAND_EPSILON};
+
+const CSV_COLUMNS: [&str; 12] = [
+    "file_path",
+    "uuid",
+    "width",
+    "height",
+    "date",
+    "latitude",
+    "longitude",
+    "altitude",
+    "make",
+    "model",
+    "software",
+    "artist",
+];
+
+/// Column names of `Metadata::csv_row`, in the same order
+pub fn csv_header() -> Vec<&'static str> {
+    CSV_COLUMNS.to_vec()
+}
+
+/// Quotes a CSV field when it contains a comma, a quote or a line break,
+/// doubling the inner quotes
+pub fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Joins already escaped fields into a CSV line (without line break)
+pub fn csv_line<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|f| f.as_ref())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quotes and escapes a string as a JSON string
+pub(crate) fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub(crate) fn json_or_null(value: Option<String>) -> String {
+    value
+        .map(|v| json_string(&v))
+        .unwrap_or_else(|| "null".to_string())
+}
+
+/// GeoJSON `FeatureCollection` of the items with plausible GPS data, one
+/// `Point` feature each with the file path, best date and camera as
+/// properties. Coordinates are in [longitude, latitude] order.
+pub fn to_geojson(items: &[Metadata]) -> String {
+    let features: Vec<String> = items
+        .iter()
+        .filter(|meta| meta.gps.is_plausible(NULL_ISLAND_EPSILON))
+        .filter_map(|meta| {
+            let (lat, lon) = meta.gps.decimal_coordinates()?;
+            Some(format!(
+                "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"Point\",\"coordinates\":[{lon},{lat}]}},\
+                \"properties\":{{\"file_path\":{},\"date\":{},\"camera\":{}}}}}",
+                json_string(&meta.file_path.display().to_string()),
+                json_or_null(meta.basics.best_date().map(|d| d.to_rfc3339())),
+                json_or_null(meta.basics.camera_name()),
+            ))
+        })
+        .collect();
+    format!(
+        "{{\"type\":\"FeatureCollection\",\"features\":[{}]}}",
+        features.join(",")
+    )
+}
+
+fn to_field<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+impl Metadata {
+    /// Escaped CSV fields of the metadata, columns given by `csv_header`.
+    /// Missing values are empty fields.
+    pub fn csv_row(&self) -> Vec<String> {
+        let coordinates = self.gps.decimal_coordinates();
+        [
+            self.file_path.display().to_string(),
+            self.uuid.clone(),
+            to_field(self.basics.width),
+            to_field(self.basics.height),
+            to_field(self.basics.best_date().map(|d| d.to_rfc3339())),
+            to_field(coordinates.map(|(lat, _)| lat)),
+            to_field(coordinates.map(|(_, lon)| lon)),
+            to_field(self.gps.altitude),
+            to_field(self.basics.make.as_ref()),
+            to_field(self.basics.model.as_ref()),
+            to_field(self.basics.software.as_ref()),
+            to_field(self.basics.artist.as_ref()),
+        ]
+        .iter()
+        .map(|f| csv_escape(f))
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::path::{Path, PathBuf};
+
+    #[rstest]
+    #[case("plain", "plain")]
+    #[case("a,b", "\"a,b\"")]
+    #[case("say \"hi\"", "\"say \"\"hi\"\"\"")]
+    #[case("two\nlines", "\"two\nlines\"")]
+    fn has_csv_escape(#[case] field: &str, #[case] expected: &str) {
+        assert_eq!(csv_escape(field), expected);
+    }
+
+    #[test]
+    fn has_csv_row() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img")
+            .join("text_icon_gps.jpg");
+        let mut meta = Metadata::from_path(&path).unwrap();
+        meta.file_path = PathBuf::from("/photos/lyon, france.jpg");
+        let row = meta.csv_row();
+        assert_eq!(row.len(), csv_header().len());
+        assert_eq!(row[0], "\"/photos/lyon, france.jpg\"");
+        assert_eq!(row[2], "3840");
+        assert_eq!(row[4], "2024-10-28T20:35:03.383+00:00");
+        assert!(row[5].starts_with("45.76"));
+        assert_eq!(row[7], "");
+        assert_eq!(row[9], "iPhone 12 Pro");
+        assert_eq!(
+            csv_line(&csv_header()),
+            "file_path,uuid,width,height,date,latitude,longitude,altitude,make,model,software,artist"
+        );
+    }
+
+    #[test]
+    fn has_geojson() {
+        let image_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img");
+        let mut items = vec![
+            Metadata::from_path(image_path.join("text_icon_gps.jpg")).unwrap(),
+            Metadata::from_path(image_path.join("text_car_animal_no-gps.png")).unwrap(),
+        ];
+        items[0].file_path = PathBuf::from("/photos/say \"lyon\".jpg");
+        let geojson = to_geojson(&items);
+        assert!(geojson.starts_with("{\"type\":\"FeatureCollection\",\"features\":[{"));
+        assert_eq!(geojson.matches("\"Feature\"").count(), 1);
+        assert!(geojson.contains("\"coordinates\":[4.8"));
+        assert!(geojson.contains("\"file_path\":\"/photos/say \\\"lyon\\\".jpg\""));
+        assert!(geojson.contains("\"date\":\"2024-10-28T20:35:03.383+00:00\""));
+        assert!(geojson.contains("\"camera\":\"Apple iPhone 12 Pro\""));
+        assert_eq!(
+            to_geojson(&[]),
+            "{\"type\":\"FeatureCollection\",\"features\":[]}"
+        );
+    }
+}
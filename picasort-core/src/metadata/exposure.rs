@@ -0,0 +1,304 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use crate::DynamicGetSet;
+use crate::metadata::exif::{
+    ExifAssignable, ExtractionSet, TagContext, extract_digital_zoom_ratio,
+    extract_exposure_program, extract_focal_length_35mm, extract_rational,
+    extract_scene_capture_type, extract_subject_distance, extract_unsigned_int16,
+    extract_unsigned_int16_vec, extract_white_balance,
+};
+pub use crate::values::{ExposureProgram, SceneCaptureType, WhiteBalance};
+
+use little_exif::exif_tag::ExifTag;
+
+#[derive(Debug, Default, Clone, PartialEq, DynamicGetSet)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exposure {
+    /// Seconds
+    pub exposure_time: Option<f64>,
+    pub f_number: Option<f64>,
+    pub iso: Option<usize>,
+    /// Millimeters
+    pub focal_length: Option<f64>,
+    /// Millimeters, focal length giving the same field of view on a 35 mm
+    /// film
+    pub focal_length_35mm: Option<usize>,
+    /// Meters, infinite when focused at infinity
+    pub subject_distance: Option<f64>,
+    /// Point (x, y), circle (x, y, diameter) or rectangle (x, y, width, height)
+    pub subject_area: Option<Vec<usize>>,
+    pub scene_capture_type: Option<SceneCaptureType>,
+    pub white_balance: Option<WhiteBalance>,
+    pub exposure_program: Option<ExposureProgram>,
+    /// 1 when no digital zoom was used
+    pub digital_zoom_ratio: Option<f64>,
+}
+
+impl Exposure {
+    /// Hyperfocal distance in meters, `H = f²/(N·c) + f`, for a circle of
+    /// confusion `coc` in millimeters (0.03 is usual for full frame)
+    pub fn hyperfocal_distance(&self, coc: f64) -> Option<f64> {
+        let focal_length = self.focal_length?;
+        let f_number = self.f_number?;
+        if focal_length <= 0.0 || f_number <= 0.0 || coc <= 0.0 {
+            return None;
+        }
+        let millimeters = focal_length * focal_length / (f_number * coc) + focal_length;
+        Some(millimeters / 1000.0)
+    }
+
+    /// The 35 mm equivalent focal length, else the focal length multiplied by
+    /// the `crop_factor` of the sensor (e.g. 1.5 for APS-C) when it is
+    /// given
+    pub fn focal_length_35mm_equivalent(&self, crop_factor: Option<f64>) -> Option<usize> {
+        if self.focal_length_35mm.is_some() {
+            return self.focal_length_35mm;
+        }
+        let focal_length = self.focal_length? * crop_factor.filter(|c| *c > 0.0)?;
+        (focal_length > 0.0).then(|| focal_length.round() as usize)
+    }
+}
+
+impl<'a> ExifAssignable<'a> for Exposure {
+    fn exif_set(&self) -> Option<ExtractionSet<'a>> {
+        Some(ExtractionSet {
+            tags: vec![
+                TagContext {
+                    destination: "exposure_time",
+                    main_tag: ExifTag::ExposureTime(Vec::new()),
+                    alternative: None,
+                    convert: extract_rational,
+                },
+                TagContext {
+                    destination: "f_number",
+                    main_tag: ExifTag::FNumber(Vec::new()),
+                    alternative: None,
+                    convert: extract_rational,
+                },
+                TagContext {
+                    destination: "iso",
+                    main_tag: ExifTag::ISO(Vec::new()),
+                    alternative: None,
+                    convert: extract_unsigned_int16,
+                },
+                TagContext {
+                    destination: "focal_length",
+                    main_tag: ExifTag::FocalLength(Vec::new()),
+                    alternative: None,
+                    convert: extract_rational,
+                },
+                TagContext {
+                    destination: "focal_length_35mm",
+                    main_tag: ExifTag::FocalLengthIn35mmFormat(Vec::new()),
+                    alternative: None,
+                    convert: extract_focal_length_35mm,
+                },
+                TagContext {
+                    destination: "subject_distance",
+                    main_tag: ExifTag::SubjectDistance(Vec::new()),
+                    alternative: None,
+                    convert: extract_subject_distance,
+                },
+                TagContext {
+                    destination: "subject_area",
+                    main_tag: ExifTag::SubjectArea(Vec::new()),
+                    alternative: None,
+                    convert: extract_unsigned_int16_vec,
+                },
+                TagContext {
+                    destination: "scene_capture_type",
+                    main_tag: ExifTag::SceneCaptureType(Vec::new()),
+                    alternative: None,
+                    convert: extract_scene_capture_type,
+                },
+                TagContext {
+                    destination: "white_balance",
+                    main_tag: ExifTag::WhiteBalance(Vec::new()),
+                    alternative: None,
+                    convert: extract_white_balance,
+                },
+                TagContext {
+                    destination: "exposure_program",
+                    main_tag: ExifTag::ExposureProgram(Vec::new()),
+                    alternative: None,
+                    convert: extract_exposure_program,
+                },
+                TagContext {
+                    destination: "digital_zoom_ratio",
+                    main_tag: ExifTag::DigitalZoomRatio(Vec::new()),
+                    alternative: None,
+                    convert: extract_digital_zoom_ratio,
+                },
+            ],
+        })
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[cfg(test)]
+mod tests {
+    use crate::metadata::{
+        exif::ExifAssignable,
+        exposure::{Exposure, ExposureProgram, SceneCaptureType, WhiteBalance},
+    };
+    use little_exif::{exif_tag::ExifTag, rational::uR64};
+    use rstest::rstest;
+
+    fn get_metadata(filename: &str) -> little_exif::metadata::Metadata {
+        use std::path::Path;
+        let image_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img")
+            .join(filename);
+        little_exif::metadata::Metadata::new_from_path(&image_path).unwrap()
+    }
+
+    #[rstest]
+    #[case(
+        "text_car_animal_no-gps.png",
+        1.0 / 160.0,
+        5.0,
+        160,
+        150.0,
+        150,
+        None,
+        Some(1.0)
+    )]
+    #[case(
+        "text_icon_gps.jpg",
+        1.0 / 50.0,
+        1.6,
+        1250,
+        4.2,
+        29,
+        Some(vec![1920, 1079, 2112, 950]),
+        None
+    )]
+    fn has_exposure(
+        #[case] filename: &str,
+        #[case] exposure_time: f64,
+        #[case] f_number: f64,
+        #[case] iso: usize,
+        #[case] focal_length: f64,
+        #[case] focal_length_35mm: usize,
+        #[case] subject_area: Option<Vec<usize>>,
+        #[case] digital_zoom_ratio: Option<f64>,
+    ) {
+        let metadata = get_metadata(filename);
+        let mut exposure = Exposure::default();
+        exposure.assign(&metadata).unwrap();
+        assert_eq!(exposure.exposure_time, Some(exposure_time));
+        assert_eq!(exposure.f_number, Some(f_number));
+        assert_eq!(exposure.iso, Some(iso));
+        assert_eq!(exposure.focal_length, Some(focal_length));
+        assert_eq!(exposure.focal_length_35mm, Some(focal_length_35mm));
+        assert_eq!(exposure.subject_distance, None);
+        assert_eq!(exposure.subject_area, subject_area);
+        assert_eq!(
+            exposure.scene_capture_type,
+            Some(SceneCaptureType::Standard)
+        );
+        assert_eq!(exposure.white_balance, Some(WhiteBalance::Auto));
+        assert_eq!(exposure.exposure_program, Some(ExposureProgram::Normal));
+        assert_eq!(exposure.digital_zoom_ratio, digital_zoom_ratio);
+    }
+
+    #[rstest]
+    #[case(Some(29), Some(4.2), None, Some(29))]
+    #[case(Some(29), Some(4.2), Some(1.5), Some(29))]
+    #[case(Some(0), Some(35.0), Some(1.5), Some(53))]
+    #[case(None, Some(35.0), None, None)]
+    #[case(None, None, Some(1.5), None)]
+    #[case(None, Some(35.0), Some(0.0), None)]
+    fn has_focal_length_35mm(
+        #[case] tag: Option<u16>,
+        #[case] focal_length: Option<f64>,
+        #[case] crop_factor: Option<f64>,
+        #[case] expected: Option<usize>,
+    ) {
+        let mut metadata = little_exif::metadata::Metadata::new();
+        if let Some(tag) = tag {
+            metadata.set_tag(ExifTag::FocalLengthIn35mmFormat(vec![tag]));
+        }
+        let mut exposure = Exposure::default();
+        exposure.assign(&metadata).unwrap();
+        assert_eq!(
+            exposure.focal_length_35mm,
+            tag.filter(|t| *t != 0).map(usize::from)
+        );
+        exposure.focal_length = focal_length;
+        assert_eq!(exposure.focal_length_35mm_equivalent(crop_factor), expected);
+    }
+
+    #[rstest]
+    #[case(0, 1, Some(1.0))]
+    #[case(0, 0, None)]
+    #[case(5, 2, Some(2.5))]
+    fn has_digital_zoom_ratio(
+        #[case] nominator: u32,
+        #[case] denominator: u32,
+        #[case] expected: Option<f64>,
+    ) {
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::DigitalZoomRatio(vec![uR64 {
+            nominator,
+            denominator,
+        }]));
+        let mut exposure = Exposure::default();
+        exposure.assign(&metadata).unwrap();
+        assert_eq!(exposure.digital_zoom_ratio, expected);
+    }
+
+    #[test]
+    fn has_unknown_shooting_modes() {
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::SceneCaptureType(vec![9]));
+        metadata.set_tag(ExifTag::WhiteBalance(vec![1]));
+        let mut exposure = Exposure::default();
+        exposure.assign(&metadata).unwrap();
+        assert_eq!(exposure.scene_capture_type, Some(SceneCaptureType::Unknown));
+        assert_eq!(exposure.white_balance, Some(WhiteBalance::Manual));
+    }
+
+    #[rstest]
+    #[case(0, 1, None)]
+    #[case(25, 10, Some(2.5))]
+    #[case(u32::MAX, 1, Some(f64::INFINITY))]
+    fn has_subject_distance(
+        #[case] nominator: u32,
+        #[case] denominator: u32,
+        #[case] expected: Option<f64>,
+    ) {
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::SubjectDistance(vec![uR64 {
+            nominator,
+            denominator,
+        }]));
+        let mut exposure = Exposure::default();
+        exposure.assign(&metadata).unwrap();
+        assert_eq!(exposure.subject_distance, expected);
+    }
+
+    #[rstest]
+    #[case(Some(50.0), Some(8.0), 0.03, Some(10.466_666))]
+    #[case(Some(150.0), Some(5.0), 0.03, Some(150.15))]
+    #[case(None, Some(8.0), 0.03, None)]
+    #[case(Some(50.0), None, 0.03, None)]
+    #[case(Some(50.0), Some(8.0), 0.0, None)]
+    fn has_hyperfocal_distance(
+        #[case] focal_length: Option<f64>,
+        #[case] f_number: Option<f64>,
+        #[case] coc: f64,
+        #[case] expected: Option<f64>,
+    ) {
+        let exposure = Exposure {
+            focal_length,
+            f_number,
+            ..Default::default()
+        };
+        match (exposure.hyperfocal_distance(coc), expected) {
+            (Some(h), Some(e)) => assert!((h - e).abs() < 1e-3, "{h} != {e}"),
+            (res, exp) => assert_eq!(res, exp),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use little_exif::filetype::FileExtension;
+
+/// Image container formats whose metadata can be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Jpeg,
+    Png,
+    Tiff,
+    Heif,
+    Webp,
+    Jxl,
+}
+
+impl ImageFormat {
+    /// Detects the format from the magic bytes at the start of a file
+    pub fn detect(header: &[u8]) -> Option<ImageFormat> {
+        match header {
+            [0xFF, 0xD8, 0xFF, ..] => Some(ImageFormat::Jpeg),
+            [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some(ImageFormat::Png),
+            [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => Some(ImageFormat::Tiff),
+            [_, _, _, _, b'f', b't', b'y', b'p', brand @ ..]
+                if [b"heic", b"heix", b"heif", b"mif1", b"avif"]
+                    .iter()
+                    .any(|b| brand.starts_with(*b)) =>
+            {
+                Some(ImageFormat::Heif)
+            }
+            [
+                b'R',
+                b'I',
+                b'F',
+                b'F',
+                _,
+                _,
+                _,
+                _,
+                b'W',
+                b'E',
+                b'B',
+                b'P',
+                ..,
+            ] => Some(ImageFormat::Webp),
+            [0x00, 0x00, 0x00, 0x0C, b'J', b'X', b'L', b' ', ..] => Some(ImageFormat::Jxl),
+            _ => None,
+        }
+    }
+
+    /// Short upper case name of the format
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "JPEG",
+            ImageFormat::Png => "PNG",
+            ImageFormat::Tiff => "TIFF",
+            ImageFormat::Heif => "HEIF",
+            ImageFormat::Webp => "WEBP",
+            ImageFormat::Jxl => "JXL",
+        }
+    }
+
+    /// The little_exif reader of the format
+    pub(crate) fn file_extension(&self) -> FileExtension {
+        match self {
+            ImageFormat::Jpeg => FileExtension::JPEG,
+            ImageFormat::Png => FileExtension::PNG {
+                as_zTXt_chunk: true,
+            },
+            ImageFormat::Tiff => FileExtension::TIFF,
+            ImageFormat::Heif => FileExtension::HEIF,
+            ImageFormat::Webp => FileExtension::WEBP,
+            ImageFormat::Jxl => FileExtension::JXL,
+        }
+    }
+}
+
+/// Name of a known format whose metadata cannot be read, from the magic
+/// bytes at the start of a file
+pub fn unsupported_format_name(header: &[u8]) -> Option<&'static str> {
+    match header {
+        [b'8', b'B', b'P', b'S', ..] => Some("PSD"),
+        [b'G', b'I', b'F', b'8', ..] => Some("GIF"),
+        [b'B', b'M', ..] => Some("BMP"),
+        [b'%', b'P', b'D', b'F', ..] => Some("PDF"),
+        [0x00, 0x00, 0x01, 0x00, ..] => Some("ICO"),
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => Some("ISO media"),
+        _ => None,
+    }
+}
+
+/// JPEG start of scan marker, the entropy coded image data follows its
+/// segment
+pub(crate) const JPEG_SOS: u8 = 0xDA;
+
+/// Marker segment of a JPEG: marker (2 bytes), length (2 bytes, big endian,
+/// counting itself), then its body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct JpegSegment<'a> {
+    pub marker: u8,
+    /// Position of the segment in the data
+    pub start: usize,
+    /// Position following the segment
+    pub end: usize,
+    pub body: &'a [u8],
+}
+
+impl<'a> JpegSegment<'a> {
+    /// Segment starting at `pos`, None when there is no marker there or the
+    /// segment is truncated
+    pub(crate) fn at(data: &'a [u8], pos: usize) -> Option<JpegSegment<'a>> {
+        let [0xFF, marker, l0, l1, ..] = data.get(pos..)? else {
+            return None;
+        };
+        let length = (u16::from_be_bytes([*l0, *l1]) as usize).checked_sub(2)?;
+        let end = pos + 4 + length;
+        Some(JpegSegment {
+            marker: *marker,
+            start: pos,
+            end,
+            body: data.get(pos + 4..end)?,
+        })
+    }
+}
+
+/// Segments of a JPEG, see [`jpeg_segments`]
+pub(crate) struct JpegSegments<'a> {
+    data: &'a [u8],
+    pos: Option<usize>,
+}
+
+/// Iterates the segments of a JPEG after its start of image marker, up to
+/// the start of scan one included. Ends at a truncated segment, empty when
+/// the data is not a JPEG.
+pub(crate) fn jpeg_segments(data: &[u8]) -> JpegSegments<'_> {
+    JpegSegments {
+        data,
+        pos: data.starts_with(&[0xFF, 0xD8]).then_some(2),
+    }
+}
+
+impl<'a> Iterator for JpegSegments<'a> {
+    type Item = JpegSegment<'a>;
+
+    fn next(&mut self) -> Option<JpegSegment<'a>> {
+        let segment = JpegSegment::at(self.data, self.pos?);
+        self.pos = segment.filter(|s| s.marker != JPEG_SOS).map(|s| s.end);
+        segment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&[0xFF, 0xD8, 0xFF, 0xE1], Some(ImageFormat::Jpeg))]
+    #[case(b"\x89PNG\r\n\x1a\n\0\0", Some(ImageFormat::Png))]
+    #[case(b"II*\0\x08\0\0\0", Some(ImageFormat::Tiff))]
+    #[case(b"MM\0*\0\0\0\x08", Some(ImageFormat::Tiff))]
+    #[case(b"\0\0\0\x18ftypheic\0\0\0\0", Some(ImageFormat::Heif))]
+    #[case(b"RIFF\0\0\0\0WEBPVP8 ", Some(ImageFormat::Webp))]
+    #[case(b"\0\0\0\x0cJXL \r\n\x87\n", Some(ImageFormat::Jxl))]
+    #[case(b"8BPS\0\x01", None)]
+    #[case(b"", None)]
+    fn has_detected_format(#[case] header: &[u8], #[case] expected: Option<ImageFormat>) {
+        assert_eq!(ImageFormat::detect(header), expected);
+    }
+
+    #[rstest]
+    #[case(b"8BPS\0\x01", Some("PSD"))]
+    #[case(b"GIF89a", Some("GIF"))]
+    #[case(b"%PDF-1.7", Some("PDF"))]
+    #[case(b"\0\0\0\x14ftypqt  ", Some("ISO media"))]
+    #[case(b"hello", None)]
+    fn has_unsupported_format_name(#[case] header: &[u8], #[case] expected: Option<&str>) {
+        assert_eq!(unsupported_format_name(header), expected);
+    }
+
+    #[test]
+    fn has_jpeg_segments() {
+        let data = b"\xFF\xD8\xFF\xE1\0\x04ab\xFF\xDA\0\x02\x12\x34\xFF\xD9";
+        let segments: Vec<_> = jpeg_segments(data).collect();
+        assert_eq!(
+            segments,
+            vec![
+                JpegSegment {
+                    marker: 0xE1,
+                    start: 2,
+                    end: 8,
+                    body: b"ab",
+                },
+                JpegSegment {
+                    marker: JPEG_SOS,
+                    start: 8,
+                    end: 12,
+                    body: b"",
+                },
+            ]
+        );
+        assert_eq!(jpeg_segments(&data[..7]).count(), 0);
+        assert_eq!(jpeg_segments(b"\x89PNG").count(), 0);
+
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img/text_icon_gps.jpg");
+        let data = std::fs::read(path).unwrap();
+        let markers: Vec<u8> = jpeg_segments(&data).map(|s| s.marker).collect();
+        assert_eq!(markers.first(), Some(&0xE1));
+        assert_eq!(markers.last(), Some(&JPEG_SOS));
+    }
+}
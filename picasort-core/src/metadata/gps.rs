@@ -1,29 +1,308 @@
 // Copyright (c) 2025 Lemur-Catta.org
 // Author: Sylvain Gubian <sgubian@lemur-catta.org>
 
+use std::path::Path;
+
 use crate::DynamicGetSet;
+use crate::error::CoreError;
 use crate::metadata::exif::{
-    ExifAssignable, ExtractionSet, TagContext, extract_gps_coord, extract_naive_date,
-    extract_naive_time, extract_string,
+    ExifAssignable, ExifWritable, ExtractionSet, TagContext, extract_flag, extract_gps_altitude,
+    extract_gps_coord, extract_naive_date, extract_naive_time, extract_prefixed_string,
+    extract_rational, extract_string,
 };
-use chrono::{NaiveDate, NaiveTime};
-use little_exif::exif_tag::ExifTag;
-
-#[derive(Debug, Default)]
-pub struct GPSCoord {
-    pub deg: usize,
-    pub min: usize,
-    pub sec: f64,
-}
+pub use crate::values::GPSCoord;
+use crate::values::{Hemisphere, web_mercator};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use little_exif::{exif_tag::ExifTag, rational::uR64};
+
+const GEOHASH_BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+const GEOHASH_MAX_PRECISION: usize = 12;
+/// Default distance in degrees (about 11 m) from (0, 0) under which a position
+/// is considered as the "null island" written by cameras without a fix
+pub const NULL_ISLAND_EPSILON: f64 = 1e-4;
 
-#[derive(Debug, Default, DynamicGetSet)]
+#[derive(Debug, Default, Clone, PartialEq, DynamicGetSet)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GPSData {
     pub latitude_ref: Option<String>,
+    #[exif(required)]
     pub latitude: Option<GPSCoord>,
     pub longitude_ref: Option<String>,
+    #[exif(required)]
     pub longitude: Option<GPSCoord>,
     pub time: Option<NaiveTime>,
     pub date: Option<NaiveDate>,
+    /// Meters, negative below sea level
+    pub altitude: Option<f64>,
+    /// How the position was found, e.g. "GPS", "CELLID" or "WLAN"
+    pub processing_method: Option<String>,
+    /// Geodetic survey data used by the receiver, e.g. "WGS-84"
+    pub map_datum: Option<String>,
+    /// Dilution of precision, the lower the better
+    pub dop: Option<f64>,
+    /// Horizontal positioning error in meters
+    pub h_positioning_error: Option<f64>,
+    /// Whether a differential correction was applied to the position
+    pub differential: Option<bool>,
+    /// Name of the area, e.g. "Parc de la Tête d'Or"
+    pub area_information: Option<String>,
+    /// IPTC city, filled by `Metadata::from_path`, `Metadata::from_bytes`
+    /// and the scans
+    pub city: Option<String>,
+    /// IPTC country name, filled as `city`
+    pub country: Option<String>,
+}
+
+/// Finder of the timezone polygons bundled with tzf, built on first use
+#[cfg(feature = "timezone")]
+pub(crate) fn timezone_finder() -> &'static tzf_rs::DefaultFinder {
+    static FINDER: std::sync::LazyLock<tzf_rs::DefaultFinder> =
+        std::sync::LazyLock::new(tzf_rs::DefaultFinder::new);
+    &FINDER
+}
+
+/// Quality of a GPS fix from its dilution of precision, a differential fix
+/// ranking one level higher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixQuality {
+    /// DOP up to 2
+    Excellent,
+    /// DOP up to 5
+    Good,
+    /// DOP up to 10
+    Moderate,
+    /// DOP above 10
+    Poor,
+}
+
+impl GPSData {
+    /// GPS data of a position given in signed decimal degrees, WGS-84 datum
+    pub fn parse_decimal(lat: f64, lon: f64) -> Result<GPSData, CoreError> {
+        if !(lat.is_finite() && lon.is_finite() && lat.abs() <= 90.0 && lon.abs() <= 180.0) {
+            return Err(CoreError::InvalidGPSData(format!("{lat}, {lon}")));
+        }
+        Ok(GPSData {
+            latitude_ref: Some(if lat < 0.0 { "S" } else { "N" }.to_string()),
+            latitude: Some(GPSCoord::from_decimal(lat)),
+            longitude_ref: Some(if lon < 0.0 { "W" } else { "E" }.to_string()),
+            longitude: Some(GPSCoord::from_decimal(lon)),
+            map_datum: Some("WGS-84".to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the signed decimal (latitude, longitude) when the data is valid
+    pub fn decimal_coordinates(&self) -> Option<(f64, f64)> {
+        if !self.is_valid() {
+            return None;
+        }
+        let mut lat = self.latitude.as_ref()?.to_decimal();
+        let mut lon = self.longitude.as_ref()?.to_decimal();
+        if self.latitude_ref.as_deref() == Some("S") {
+            lat = -lat;
+        }
+        if matches!(self.longitude_ref.as_deref(), Some("W") | Some("O")) {
+            lon = -lon;
+        }
+        Some((lat, lon))
+    }
+
+    /// Tells if the data is valid, within the latitude and longitude ranges and
+    /// farther than `epsilon` degrees from the (0, 0) "null island"
+    pub fn is_plausible(&self, epsilon: f64) -> bool {
+        let Some((lat, lon)) = self.decimal_coordinates() else {
+            return false;
+        };
+        if lat.abs() > 90.0 || lon.abs() > 180.0 {
+            return false;
+        }
+        lat.abs() > epsilon || lon.abs() > epsilon
+    }
+
+    /// Classifies the dilution of precision, one level higher for a
+    /// differential fix, None when it is unknown
+    pub fn fix_quality(&self) -> Option<FixQuality> {
+        let dop = self.dop.filter(|d| *d >= 0.0)?;
+        let quality = match dop {
+            d if d <= 2.0 => FixQuality::Excellent,
+            d if d <= 5.0 => FixQuality::Good,
+            d if d <= 10.0 => FixQuality::Moderate,
+            _ => FixQuality::Poor,
+        };
+        if self.differential != Some(true) {
+            return Some(quality);
+        }
+        Some(match quality {
+            FixQuality::Excellent | FixQuality::Good => FixQuality::Excellent,
+            FixQuality::Moderate => FixQuality::Good,
+            FixQuality::Poor => FixQuality::Moderate,
+        })
+    }
+
+    /// GPS date and time, always UTC, None when either is missing. Unlike the
+    /// EXIF dates it does not depend on the camera clock.
+    pub fn datetime_utc(&self) -> Option<DateTime<Utc>> {
+        Some(self.date?.and_time(self.time?).and_utc())
+    }
+
+    /// Whether the horizontal positioning error is at most `max_error`
+    /// meters, None when it is unknown. A more direct measure than the fix
+    /// quality when the device writes it.
+    pub fn within_error(&self, max_error: f64) -> Option<bool> {
+        let error = self.h_positioning_error.filter(|e| *e >= 0.0)?;
+        Some(error <= max_error)
+    }
+
+    /// Hemisphere of the latitude, to pick the seasons of `Basics::season`
+    pub fn hemisphere(&self) -> Option<Hemisphere> {
+        let (lat, _) = self.decimal_coordinates()?;
+        Some(if lat < 0.0 {
+            Hemisphere::South
+        } else {
+            Hemisphere::North
+        })
+    }
+
+    /// Looks up the timezone where the image was taken
+    #[cfg(feature = "timezone")]
+    pub fn timezone(&self) -> Option<chrono_tz::Tz> {
+        let (lat, lon) = self.decimal_coordinates()?;
+        timezone_finder().get_tz_name(lon, lat).parse().ok()
+    }
+
+    /// Projects the coordinates to Web Mercator (EPSG:3857) meters
+    pub fn to_web_mercator(&self) -> Option<(f64, f64)> {
+        let (lat, lon) = self.decimal_coordinates()?;
+        Some(web_mercator(lat, lon))
+    }
+
+    /// Computes the base-32 geohash of the coordinates, precision is clamped to 12
+    pub fn geohash(&self, precision: usize) -> Option<String> {
+        let (lat, lon) = self.decimal_coordinates()?;
+        let precision = precision.min(GEOHASH_MAX_PRECISION);
+
+        let mut lat_range = (-90.0, 90.0);
+        let mut lon_range = (-180.0, 180.0);
+        let mut hash = String::with_capacity(precision);
+        let mut even_bit = true;
+        let mut bits = 0;
+        let mut index = 0;
+
+        while hash.len() < precision {
+            let (range, value) = if even_bit {
+                (&mut lon_range, lon)
+            } else {
+                (&mut lat_range, lat)
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            index <<= 1;
+            if value >= mid {
+                index |= 1;
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            even_bit = !even_bit;
+            bits += 1;
+            if bits == 5 {
+                hash.push(GEOHASH_BASE32[index] as char);
+                bits = 0;
+                index = 0;
+            }
+        }
+        Some(hash)
+    }
+}
+
+/// Rationals of a coordinate, the seconds to the thousandth
+fn coord_rationals(coord: &GPSCoord) -> Vec<uR64> {
+    let rational = |nominator, denominator| uR64 {
+        nominator,
+        denominator,
+    };
+    vec![
+        rational(coord.deg as u32, 1),
+        rational(coord.min as u32, 1),
+        rational((coord.sec * 1000.0).round() as u32, 1000),
+    ]
+}
+
+/// Rational of a positive value, to the thousandth
+fn thousandths(value: f64) -> Vec<uR64> {
+    vec![uR64 {
+        nominator: (value * 1000.0).round() as u32,
+        denominator: 1000,
+    }]
+}
+
+impl ExifWritable for GPSData {
+    fn exif_tags(&self) -> Vec<ExifTag> {
+        let mut tags = Vec::new();
+        if let Some(r) = &self.latitude_ref {
+            tags.push(ExifTag::GPSLatitudeRef(r.clone()));
+        }
+        if let Some(c) = &self.latitude {
+            tags.push(ExifTag::GPSLatitude(coord_rationals(c)));
+        }
+        if let Some(r) = &self.longitude_ref {
+            tags.push(ExifTag::GPSLongitudeRef(r.clone()));
+        }
+        if let Some(c) = &self.longitude {
+            tags.push(ExifTag::GPSLongitude(coord_rationals(c)));
+        }
+        if let Some(altitude) = self.altitude {
+            tags.push(ExifTag::GPSAltitudeRef(vec![u8::from(altitude < 0.0)]));
+            tags.push(ExifTag::GPSAltitude(thousandths(altitude.abs())));
+        }
+        if let Some(datum) = &self.map_datum {
+            tags.push(ExifTag::GPSMapDatum(datum.clone()));
+        }
+        if let Some(dop) = self.dop {
+            tags.push(ExifTag::GPSDOP(thousandths(dop)));
+        }
+        if let Some(error) = self.h_positioning_error {
+            tags.push(ExifTag::GPSHPositioningError(thousandths(error)));
+        }
+        if let Some(differential) = self.differential {
+            tags.push(ExifTag::GPSDifferential(vec![u16::from(differential)]));
+        }
+        tags
+    }
+}
+
+/// Writes the position, in signed decimal degrees, to the EXIF of the file
+/// and checks it by reading it back. The other tags are kept.
+pub fn apply_gps(path: &Path, lat: f64, lon: f64) -> Result<(), CoreError> {
+    let gps = GPSData::parse_decimal(lat, lon)?;
+    let mut exif = little_exif::metadata::Metadata::new_from_path(path)?;
+    exif.set_tag(ExifTag::GPSVersionID(vec![2, 3, 0, 0]));
+    for tag in gps.exif_tags() {
+        exif.set_tag(tag);
+    }
+    exif.write_to_file(path)?;
+
+    let mut written = GPSData::default();
+    written
+        .assign(&little_exif::metadata::Metadata::new_from_path(path)?)
+        .map_err(|e| CoreError::InvalidEXIFConversion(e.to_string()))?;
+    match written.decimal_coordinates() {
+        Some((w_lat, w_lon)) if (w_lat - lat).abs() < 1e-6 && (w_lon - lon).abs() < 1e-6 => Ok(()),
+        _ => Err(CoreError::InvalidGPSData(format!(
+            "{lat}, {lon} not written to {}",
+            path.display()
+        ))),
+    }
+}
+
+/// Reads only the GPS data of the file, without hashing it nor assigning the
+/// other structs of `Metadata`, e.g. to place a library on a map. None when
+/// the file has no valid GPS position.
+pub fn gps_only(path: &Path) -> Result<Option<GPSData>, CoreError> {
+    let exif = little_exif::metadata::Metadata::new_from_path(path)?;
+    let mut gps = GPSData::default();
+    gps.assign(&exif)
+        .map_err(|e| CoreError::InvalidEXIFConversion(e.to_string()))?;
+    Ok(gps.is_valid().then_some(gps))
 }
 
 impl<'a> ExifAssignable<'a> for GPSData {
@@ -37,13 +316,11 @@ impl<'a> ExifAssignable<'a> for GPSData {
         if let Some(long) = &self.longitude_ref
             && long.as_str() != "O"
             && long.as_str() != "E"
+            && long.as_str() != "W"
         {
             return false;
         }
-        if self.latitude.is_none() || self.longitude.is_none() {
-            return false;
-        }
-        true
+        self.missing_required().is_empty()
     }
 
     fn exif_set(&self) -> Option<ExtractionSet<'a>> {
@@ -85,6 +362,48 @@ impl<'a> ExifAssignable<'a> for GPSData {
                     alternative: None,
                     convert: extract_naive_date,
                 },
+                TagContext {
+                    destination: "altitude",
+                    main_tag: ExifTag::GPSAltitude(Vec::new()),
+                    alternative: None,
+                    convert: extract_gps_altitude,
+                },
+                TagContext {
+                    destination: "processing_method",
+                    main_tag: ExifTag::GPSProcessingMethod(Vec::new()),
+                    alternative: None,
+                    convert: extract_prefixed_string,
+                },
+                TagContext {
+                    destination: "map_datum",
+                    main_tag: ExifTag::GPSMapDatum(String::new()),
+                    alternative: None,
+                    convert: extract_string,
+                },
+                TagContext {
+                    destination: "dop",
+                    main_tag: ExifTag::GPSDOP(Vec::new()),
+                    alternative: None,
+                    convert: extract_rational,
+                },
+                TagContext {
+                    destination: "h_positioning_error",
+                    main_tag: ExifTag::GPSHPositioningError(Vec::new()),
+                    alternative: None,
+                    convert: extract_rational,
+                },
+                TagContext {
+                    destination: "differential",
+                    main_tag: ExifTag::GPSDifferential(Vec::new()),
+                    alternative: None,
+                    convert: extract_flag,
+                },
+                TagContext {
+                    destination: "area_information",
+                    main_tag: ExifTag::GPSAreaInformation(Vec::new()),
+                    alternative: None,
+                    convert: extract_prefixed_string,
+                },
             ],
         })
     }
@@ -93,11 +412,13 @@ impl<'a> ExifAssignable<'a> for GPSData {
 #[allow(clippy::too_many_arguments)]
 #[cfg(test)]
 mod tests {
+    use crate::test_utils::TempDir;
     use chrono::NaiveDate;
     use chrono::NaiveTime;
     use rstest::rstest;
 
     use crate::metadata::exif::ExifAssignable;
+    use crate::metadata::gps::FixQuality;
 
     fn get_metadata(filename: &str) -> little_exif::metadata::Metadata {
         use std::path::Path;
@@ -193,5 +514,299 @@ mod tests {
             panic!("Error when assigning");
         }
         assert_eq!(gps_data.is_valid(), expected);
+        assert_eq!(
+            gps_data.hemisphere(),
+            expected.then_some(crate::values::Hemisphere::North)
+        );
+    }
+
+    #[rstest]
+    #[case("text_car_animal_no-gps.png", 5, None)]
+    #[case("text_icon_gps.jpg", 5, Some("u05kq"))]
+    #[case("text_icon_gps.jpg", 12, Some("u05kq6rvhbek"))]
+    #[case("text_icon_gps.jpg", 20, Some("u05kq6rvhbek"))]
+    fn has_geohash(
+        #[case] filename: &str,
+        #[case] precision: usize,
+        #[case] expected: Option<&str>,
+    ) {
+        use crate::metadata::gps::GPSData;
+
+        let metadata = get_metadata(filename);
+        let mut gps_data = GPSData::default();
+        let res = gps_data.assign(&metadata);
+        if res.is_err() {
+            panic!("Error when assigning");
+        }
+        assert_eq!(gps_data.geohash(precision).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(None, 4305, Some(430.5))]
+    #[case(Some(0), 4305, Some(430.5))]
+    #[case(Some(1), 4305, Some(-430.5))]
+    fn has_altitude(
+        #[case] reference: Option<u8>,
+        #[case] decimeters: u32,
+        #[case] expected: Option<f64>,
+    ) {
+        use crate::metadata::gps::GPSData;
+        use little_exif::{exif_tag::ExifTag, rational::uR64};
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::GPSAltitude(vec![uR64 {
+            nominator: decimeters,
+            denominator: 10,
+        }]));
+        if let Some(r) = reference {
+            metadata.set_tag(ExifTag::GPSAltitudeRef(vec![r]));
+        }
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&metadata).unwrap();
+        assert_eq!(gps_data.altitude, expected);
+    }
+
+    #[test]
+    fn has_no_time_on_short_timestamp() {
+        use crate::metadata::gps::GPSData;
+        use little_exif::{exif_tag::ExifTag, rational::uR64};
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::GPSTimeStamp(vec![
+            uR64 {
+                nominator: 11,
+                denominator: 1,
+            },
+            uR64 {
+                nominator: 33,
+                denominator: 1,
+            },
+        ]));
+        metadata.set_tag(ExifTag::GPSDateStamp("2024:10:29".to_string()));
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&metadata).unwrap();
+        assert_eq!(gps_data.time, None);
+        assert_eq!(gps_data.date, NaiveDate::from_ymd_opt(2024, 10, 29));
+    }
+
+    #[rstest]
+    #[case("text_icon_gps.jpg", Some("2024-10-29T11:33:25Z"))]
+    #[case("text_car_animal_no-gps.png", None)]
+    fn has_datetime_utc(#[case] filename: &str, #[case] expected: Option<&str>) {
+        use crate::metadata::gps::GPSData;
+
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&get_metadata(filename)).unwrap();
+        assert_eq!(
+            gps_data
+                .datetime_utc()
+                .map(|d| d.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            expected.map(str::to_string)
+        );
+        gps_data.time = None;
+        assert_eq!(gps_data.datetime_utc(), None);
+    }
+
+    #[cfg(feature = "timezone")]
+    #[rstest]
+    #[case("text_car_animal_no-gps.png", None)]
+    #[case("text_icon_gps.jpg", Some(chrono_tz::Europe::Paris))]
+    fn has_timezone(#[case] filename: &str, #[case] expected: Option<chrono_tz::Tz>) {
+        use crate::metadata::gps::GPSData;
+
+        let metadata = get_metadata(filename);
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&metadata).unwrap();
+        assert_eq!(gps_data.timezone(), expected);
+    }
+
+    #[rstest]
+    #[case("text_car_animal_no-gps.png", None)]
+    #[case("text_icon_gps.jpg", Some((540_547.657, 5_742_018.651)))]
+    fn has_web_mercator(#[case] filename: &str, #[case] expected: Option<(f64, f64)>) {
+        use crate::metadata::gps::GPSData;
+
+        let metadata = get_metadata(filename);
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&metadata).unwrap();
+        match (gps_data.to_web_mercator(), expected) {
+            (Some((x, y)), Some((ex, ey))) => {
+                assert!((x - ex).abs() < 0.01 && (y - ey).abs() < 0.01)
+            }
+            (res, exp) => assert_eq!(res, exp),
+        }
+    }
+
+    #[rstest]
+    #[case(b"ASCII\0\0\0GPS".to_vec(), Some("GPS"))]
+    #[case(b"\0\0\0\0\0\0\0\0CELLID".to_vec(), Some("CELLID"))]
+    #[case(b"WLAN\0".to_vec(), Some("WLAN"))]
+    #[case(b"ASCII\0\0\0".to_vec(), None)]
+    fn has_processing_method(#[case] raw: Vec<u8>, #[case] expected: Option<&str>) {
+        use crate::metadata::gps::GPSData;
+        use little_exif::exif_tag::ExifTag;
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        metadata.set_tag(ExifTag::GPSProcessingMethod(raw));
+        metadata.set_tag(ExifTag::GPSMapDatum("WGS-84".to_string()));
+        metadata.set_tag(ExifTag::GPSAreaInformation(b"ASCII\0\0\0Lyon".to_vec()));
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&metadata).unwrap();
+        assert_eq!(gps_data.processing_method.as_deref(), expected);
+        assert_eq!(gps_data.area_information.as_deref(), Some("Lyon"));
+        assert_eq!(gps_data.map_datum.as_deref(), Some("WGS-84"));
+    }
+
+    #[rstest]
+    #[case((0, 0, 0.0), (0, 0, 0.0), false)]
+    #[case((0, 0, 0.1), (0, 0, 0.2), false)]
+    #[case((0, 0, 1.0), (0, 0, 0.0), true)]
+    #[case((91, 0, 0.0), (4, 51, 20.96), false)]
+    #[case((45, 45, 37.05), (180, 30, 0.0), false)]
+    #[case((45, 45, 37.05), (4, 51, 20.96), true)]
+    fn has_plausibility(
+        #[case] latitude: (usize, usize, f64),
+        #[case] longitude: (usize, usize, f64),
+        #[case] expected: bool,
+    ) {
+        use crate::metadata::gps::{GPSCoord, GPSData, NULL_ISLAND_EPSILON};
+
+        let coord = |(deg, min, sec)| GPSCoord { deg, min, sec };
+        let gps_data = GPSData {
+            latitude_ref: Some("N".to_string()),
+            latitude: Some(coord(latitude)),
+            longitude_ref: Some("E".to_string()),
+            longitude: Some(coord(longitude)),
+            ..Default::default()
+        };
+        assert!(gps_data.is_valid());
+        assert_eq!(gps_data.is_plausible(NULL_ISLAND_EPSILON), expected);
+    }
+
+    #[rstest]
+    #[case(None, None)]
+    #[case(Some((15, 10)), Some(FixQuality::Excellent))]
+    #[case(Some((5, 1)), Some(FixQuality::Good))]
+    #[case(Some((87, 10)), Some(FixQuality::Moderate))]
+    #[case(Some((20, 1)), Some(FixQuality::Poor))]
+    fn has_fix_quality(#[case] dop: Option<(u32, u32)>, #[case] expected: Option<FixQuality>) {
+        use crate::metadata::gps::GPSData;
+        use little_exif::{exif_tag::ExifTag, rational::uR64};
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        if let Some((nominator, denominator)) = dop {
+            metadata.set_tag(ExifTag::GPSDOP(vec![uR64 {
+                nominator,
+                denominator,
+            }]));
+        }
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&metadata).unwrap();
+        assert_eq!(gps_data.dop, dop.map(|(n, d)| n as f64 / d as f64));
+        assert_eq!(gps_data.fix_quality(), expected);
+    }
+
+    #[rstest]
+    #[case(None, None, None, None)]
+    #[case(None, Some(1), Some(true), None)]
+    #[case(Some((5, 1)), None, None, Some(FixQuality::Good))]
+    #[case(Some((5, 1)), Some(0), Some(false), Some(FixQuality::Good))]
+    #[case(Some((5, 1)), Some(1), Some(true), Some(FixQuality::Excellent))]
+    #[case(Some((15, 10)), Some(1), Some(true), Some(FixQuality::Excellent))]
+    #[case(Some((20, 1)), Some(1), Some(true), Some(FixQuality::Moderate))]
+    #[case(Some((20, 1)), Some(7), None, Some(FixQuality::Poor))]
+    fn has_differential_fix_quality(
+        #[case] dop: Option<(u32, u32)>,
+        #[case] raw: Option<u16>,
+        #[case] differential: Option<bool>,
+        #[case] expected: Option<FixQuality>,
+    ) {
+        use crate::metadata::exif::ExifWritable;
+        use crate::metadata::gps::GPSData;
+        use little_exif::{exif_tag::ExifTag, rational::uR64};
+
+        let mut metadata = little_exif::metadata::Metadata::new();
+        if let Some((nominator, denominator)) = dop {
+            metadata.set_tag(ExifTag::GPSDOP(vec![uR64 {
+                nominator,
+                denominator,
+            }]));
+        }
+        if let Some(raw) = raw {
+            metadata.set_tag(ExifTag::GPSDifferential(vec![raw]));
+        }
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&metadata).unwrap();
+        assert_eq!(gps_data.differential, differential);
+        assert_eq!(gps_data.fix_quality(), expected);
+        assert_eq!(
+            gps_data
+                .exif_tags()
+                .iter()
+                .any(|t| matches!(t, ExifTag::GPSDifferential(_))),
+            differential.is_some()
+        );
+    }
+
+    #[rstest]
+    #[case("text_icon_gps.jpg", 50.0, Some(1.0), Some(true))]
+    #[case("text_icon_gps.jpg", 0.5, Some(1.0), Some(false))]
+    #[case("text_car_animal_no-gps.png", 50.0, None, None)]
+    fn has_h_positioning_error(
+        #[case] filename: &str,
+        #[case] max_error: f64,
+        #[case] error: Option<f64>,
+        #[case] expected: Option<bool>,
+    ) {
+        use crate::metadata::gps::GPSData;
+
+        let mut gps_data = GPSData::default();
+        gps_data.assign(&get_metadata(filename)).unwrap();
+        assert_eq!(gps_data.h_positioning_error, error);
+        assert_eq!(gps_data.within_error(max_error), expected);
+    }
+
+    #[rstest]
+    #[case("text_icon_gps.jpg", true)]
+    #[case("text_car_animal_no-gps.png", false)]
+    fn has_gps_only(#[case] filename: &str, #[case] expected: bool) {
+        use crate::metadata::{Metadata, gps_only};
+        use std::path::Path;
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img")
+            .join(filename);
+        let gps = gps_only(&path).unwrap();
+        assert_eq!(gps.is_some(), expected);
+        if let Some(gps) = gps {
+            assert_eq!(gps, Metadata::from_path(&path).unwrap().gps);
+        }
+        assert!(gps_only(&path.with_file_name("missing.jpg")).is_err());
+    }
+
+    #[rstest]
+    #[case("text_car_animal_no-gps.png", -33.856_784, 151.215_297)]
+    #[case("text_icon_gps.jpg", 40.689_247, -74.044_502)]
+    fn has_applied_gps(#[case] filename: &str, #[case] lat: f64, #[case] lon: f64) {
+        use crate::metadata::{Metadata, apply_gps};
+        use std::{fs, path::Path};
+
+        let root = TempDir::new();
+        let path = root.join(filename);
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img")
+            .join(filename);
+        fs::copy(&source, &path).unwrap();
+
+        apply_gps(&path, lat, lon).unwrap();
+        let meta = Metadata::from_path(&path).unwrap();
+        let (w_lat, w_lon) = meta.gps.decimal_coordinates().unwrap();
+        assert!((w_lat - lat).abs() < 1e-6 && (w_lon - lon).abs() < 1e-6);
+        assert_eq!(meta.gps.map_datum.as_deref(), Some("WGS-84"));
+        let original = Metadata::from_path(&source).unwrap();
+        assert_eq!(meta.basics.original_date, original.basics.original_date);
+
+        assert!(apply_gps(&path, 91.0, 0.0).is_err());
+        assert!(apply_gps(&path, f64::NAN, 0.0).is_err());
     }
 }
@@ -0,0 +1,210 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+// Geotagging from the track of a GPS logger. Only the track points of the
+// GPX file are read, with a minimal scanner rather than a full XML parser.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::error::CoreError;
+use crate::metadata::Metadata;
+use crate::metadata::exif::ExifAssignable;
+use crate::values::GPSCoord;
+
+/// Timed positions of a GPX file, sorted by time
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GpxTrack {
+    /// Time, decimal latitude and longitude
+    pub points: Vec<(DateTime<Utc>, f64, f64)>,
+}
+
+impl GpxTrack {
+    /// Reads the `trkpt` elements of a GPX document. Points without a time are
+    /// skipped, a point with an unreadable position is an error.
+    pub fn parse(xml: &str) -> Result<GpxTrack, CoreError> {
+        let mut points = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find("<trkpt") {
+            rest = &rest[start + "<trkpt".len()..];
+            let head_end = rest
+                .find('>')
+                .ok_or_else(|| CoreError::InvalidGPSData("Unterminated trkpt".to_string()))?;
+            let head = &rest[..head_end];
+            let body = match head.ends_with('/') {
+                true => "",
+                false => rest[head_end..]
+                    .find("</trkpt>")
+                    .map_or("", |end| &rest[head_end..head_end + end]),
+            };
+            let lat = parse_coordinate(head, "lat", 90.0)?;
+            let lon = parse_coordinate(head, "lon", 180.0)?;
+            if let Some(time) =
+                element_text(body, "time").and_then(|t| DateTime::parse_from_rfc3339(t.trim()).ok())
+            {
+                points.push((time.to_utc(), lat, lon));
+            }
+        }
+        points.sort_by_key(|(time, _, _)| *time);
+        Ok(GpxTrack { points })
+    }
+
+    /// Reads and parses a GPX file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<GpxTrack, CoreError> {
+        GpxTrack::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Position at `time`, linearly interpolated between the surrounding
+    /// points. None when the time is outside of the track.
+    pub fn position_at(&self, time: DateTime<Utc>) -> Option<(f64, f64)> {
+        let index = self.points.partition_point(|(t, _, _)| *t < time);
+        let (after_time, after_lat, after_lon) = *self.points.get(index)?;
+        if after_time == time {
+            return Some((after_lat, after_lon));
+        }
+        let (before_time, before_lat, before_lon) = *self.points.get(index.checked_sub(1)?)?;
+        let span = (after_time - before_time).num_milliseconds() as f64;
+        let ratio = (time - before_time).num_milliseconds() as f64 / span;
+        Some((
+            before_lat + (after_lat - before_lat) * ratio,
+            before_lon + (after_lon - before_lon) * ratio,
+        ))
+    }
+}
+
+/// Fills the GPS data of `meta` with its position on `track` at its best date.
+/// Images already located, without a date or taken outside of the track are
+/// left untouched, false is returned then.
+///
+/// A best date with an EXIF offset is converted to UTC with it, and
+/// `time_offset` is ignored. Otherwise EXIF dates are the local time of the
+/// camera, they are compared with the UTC times of the track once shifted by
+/// `time_offset`: a camera 47 s ahead of GPS time on a UTC+1 clock needs an
+/// offset of -1 h 0 m 47 s.
+pub fn geotag_from_gpx(meta: &mut Metadata, track: &GpxTrack, time_offset: TimeDelta) -> bool {
+    if meta.gps.is_valid() {
+        return false;
+    }
+    let Some(time) = meta.basics.best_utc_date().or_else(|| {
+        meta.basics
+            .best_date()
+            .and_then(|d| d.checked_add_signed(time_offset))
+    }) else {
+        return false;
+    };
+    let Some((lat, lon)) = track.position_at(time) else {
+        return false;
+    };
+    let gps = &mut meta.gps;
+    gps.latitude_ref = Some(if lat < 0.0 { "S" } else { "N" }.to_string());
+    gps.latitude = Some(GPSCoord::from_decimal(lat));
+    gps.longitude_ref = Some(if lon < 0.0 { "W" } else { "E" }.to_string());
+    gps.longitude = Some(GPSCoord::from_decimal(lon));
+    gps.date = Some(time.date_naive());
+    gps.time = Some(time.time());
+    true
+}
+
+fn parse_coordinate(head: &str, name: &str, limit: f64) -> Result<f64, CoreError> {
+    attribute(head, name)
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.abs() <= limit)
+        .ok_or_else(|| CoreError::InvalidGPSData(format!("Invalid trkpt {name} in <trkpt{head}>")))
+}
+
+/// Value of the `name` attribute, quoted with ' or "
+fn attribute<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.split_whitespace().find_map(|pair| {
+        let value = pair.strip_prefix(name)?.strip_prefix('=')?;
+        let value = value.trim_end_matches('/');
+        let quote = value.chars().next().filter(|q| *q == '"' || *q == '\'')?;
+        value[1..].strip_suffix(quote)
+    })
+}
+
+fn element_text<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+    let start = body.find(&format!("<{name}>"))? + name.len() + 2;
+    let end = body[start..].find(&format!("</{name}>"))?;
+    Some(&body[start..start + end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+    use rstest::rstest;
+
+    const GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="logger">
+  <trk><trkseg>
+    <trkpt lat="45.0" lon="4.0"><ele>170</ele><time>2024-10-29T11:00:00Z</time></trkpt>
+    <trkpt lon='5.0' lat='46.0'><time>2024-10-29T12:00:00Z</time></trkpt>
+    <trkpt lat="47.0" lon="6.0"/>
+  </trkseg></trk>
+</gpx>"#;
+
+    fn utc(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date).unwrap().to_utc()
+    }
+
+    #[test]
+    fn has_parsed_track() {
+        let track = GpxTrack::parse(GPX).unwrap();
+        assert_eq!(
+            track.points,
+            vec![
+                (utc("2024-10-29T11:00:00Z"), 45.0, 4.0),
+                (utc("2024-10-29T12:00:00Z"), 46.0, 5.0),
+            ]
+        );
+        assert!(GpxTrack::parse(r#"<trkpt lat="95" lon="4"/>"#).is_err());
+        assert!(GpxTrack::parse(r#"<trkpt lat="45"/>"#).is_err());
+    }
+
+    #[rstest]
+    #[case("2024-10-29T11:00:00Z", Some((45.0, 4.0)))]
+    #[case("2024-10-29T11:30:00Z", Some((45.5, 4.5)))]
+    #[case("2024-10-29T12:00:00Z", Some((46.0, 5.0)))]
+    #[case("2024-10-29T10:59:59Z", None)]
+    #[case("2024-10-29T12:00:01Z", None)]
+    fn has_position_at(#[case] time: &str, #[case] expected: Option<(f64, f64)>) {
+        let track = GpxTrack::parse(GPX).unwrap();
+        assert_eq!(track.position_at(utc(time)), expected);
+    }
+
+    #[test]
+    fn has_geotag() {
+        let track = GpxTrack::parse(GPX).unwrap();
+        let mut meta = Metadata::default();
+        assert!(!geotag_from_gpx(&mut meta, &track, TimeDelta::zero()));
+
+        meta.basics.original_date = Some(utc("2024-10-29T13:00:00Z"));
+        assert!(!geotag_from_gpx(&mut meta, &track, TimeDelta::zero()));
+        assert!(meta.gps.latitude.is_none());
+
+        // The camera is 47 seconds ahead
+        meta.basics.original_date = Some(utc("2024-10-29T11:30:47Z"));
+        assert!(geotag_from_gpx(&mut meta, &track, TimeDelta::seconds(-47)));
+        let (lat, lon) = meta.gps.decimal_coordinates().unwrap();
+        assert!((lat - 45.5).abs() < 1e-9 && (lon - 4.5).abs() < 1e-9);
+        assert_eq!(meta.gps.time, Some(utc("2024-10-29T11:30:00Z").time()));
+        // Already located
+        assert!(!geotag_from_gpx(&mut meta, &track, TimeDelta::zero()));
+    }
+
+    #[test]
+    fn has_geotag_with_offset() {
+        let track = GpxTrack::parse(GPX).unwrap();
+        let mut meta = Metadata::default();
+        // 12:30 on a UTC+1 camera clock, the offset given for a local clock
+        // is not applied
+        meta.basics.original_date = Some(utc("2024-10-29T12:30:00Z"));
+        meta.basics.original_offset = FixedOffset::east_opt(3600);
+        assert!(geotag_from_gpx(&mut meta, &track, TimeDelta::hours(-2)));
+        let (lat, lon) = meta.gps.decimal_coordinates().unwrap();
+        assert!((lat - 45.5).abs() < 1e-9 && (lon - 4.5).abs() < 1e-9);
+        assert_eq!(meta.gps.time, Some(utc("2024-10-29T11:30:00Z").time()));
+    }
+}
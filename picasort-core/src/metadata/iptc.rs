@@ -0,0 +1,181 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+// IPTC-IIM records, stored in a Photoshop "8BIM" image resource block: in the
+// APP13 segment of JPEG files, or hex encoded in the "Raw profile type iptc"
+// text chunk written by ImageMagick and exiftool in PNG files.
+
+use crate::metadata::format::jpeg_segments;
+use crate::metadata::gps::GPSData;
+
+/// IIM dataset of the city, record 2
+pub const IPTC_CITY: (u8, u8) = (2, 90);
+/// IIM dataset of the country name, record 2
+pub const IPTC_COUNTRY: (u8, u8) = (2, 101);
+
+const PHOTOSHOP_SIGNATURE: &[u8] = b"Photoshop 3.0\0";
+const IPTC_RESOURCE_ID: u16 = 0x0404;
+
+/// An IIM dataset: record number, dataset number and value
+pub type IptcDataset = (u8, u8, Vec<u8>);
+
+/// Reads the IPTC datasets of the APP13 segments of a JPEG file
+pub fn from_jpeg(data: &[u8]) -> Vec<IptcDataset> {
+    jpeg_segments(data)
+        .filter(|segment| segment.marker == 0xED)
+        .filter_map(|segment| segment.body.strip_prefix(PHOTOSHOP_SIGNATURE))
+        .flat_map(|resources| parse_datasets(iptc_resource(resources).unwrap_or_default()))
+        .collect()
+}
+
+/// Reads the IPTC datasets of the PNG text chunks
+pub fn from_png_text_chunks(chunks: &[(String, String)]) -> Vec<IptcDataset> {
+    chunks
+        .iter()
+        .filter(|(keyword, _)| keyword == "Raw profile type iptc")
+        .filter_map(|(_, text)| decode_raw_profile(text))
+        .flat_map(|resources| parse_datasets(iptc_resource(&resources).unwrap_or_default()))
+        .collect()
+}
+
+/// Fills the city and country of `gps` when they are in the datasets
+pub fn assign_location(gps: &mut GPSData, datasets: &[IptcDataset]) {
+    for (record, dataset, value) in datasets {
+        let field = match (*record, *dataset) {
+            IPTC_CITY => &mut gps.city,
+            IPTC_COUNTRY => &mut gps.country,
+            _ => continue,
+        };
+        let text = String::from_utf8_lossy(value).trim().to_string();
+        if field.is_none() && !text.is_empty() {
+            *field = Some(text);
+        }
+    }
+}
+
+/// Raw profile: a line break, the type, the length, then the hex encoded data
+fn decode_raw_profile(text: &str) -> Option<Vec<u8>> {
+    let mut lines = text.trim_start().lines();
+    lines.next()?;
+    let length: usize = lines.next()?.trim().parse().ok()?;
+    let hex: Vec<u8> = lines
+        .flat_map(|line| line.bytes())
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    let bytes: Option<Vec<u8>> = hex
+        .chunks_exact(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect();
+    bytes.filter(|b| b.len() >= length)
+}
+
+/// Data of the IPTC block of Photoshop image resources
+fn iptc_resource(mut resources: &[u8]) -> Option<&[u8]> {
+    // Resource: "8BIM", id (2 bytes), Pascal name padded to an even size,
+    // data size (4 bytes), data padded to an even size
+    while let [b'8', b'B', b'I', b'M', i0, i1, name_length, rest @ ..] = resources {
+        let name_size = (*name_length as usize + 1).next_multiple_of(2);
+        let rest = rest.get(name_size - 1..)?;
+        let [s0, s1, s2, s3, rest @ ..] = rest else {
+            return None;
+        };
+        let size = u32::from_be_bytes([*s0, *s1, *s2, *s3]) as usize;
+        let data = rest.get(..size)?;
+        if u16::from_be_bytes([*i0, *i1]) == IPTC_RESOURCE_ID {
+            return Some(data);
+        }
+        resources = rest.get(size.next_multiple_of(2)..)?;
+    }
+    None
+}
+
+fn parse_datasets(mut data: &[u8]) -> Vec<IptcDataset> {
+    let mut datasets = Vec::new();
+    // Dataset: tag marker 0x1C, record, dataset, size (2 bytes), value.
+    // Extended sizes (above 32767 bytes) are not used by text datasets.
+    while let [0x1C, record, dataset, s0, s1, rest @ ..] = data {
+        let size = u16::from_be_bytes([*s0, *s1]) as usize;
+        let Some(value) = rest.get(..size) else {
+            break;
+        };
+        datasets.push((*record, *dataset, value.to_vec()));
+        data = &rest[size..];
+    }
+    datasets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::path::Path;
+
+    fn photoshop_resources(datasets: &[(u8, u8, &str)]) -> Vec<u8> {
+        let mut iim = Vec::new();
+        for (record, dataset, value) in datasets {
+            iim.extend([0x1C, *record, *dataset]);
+            iim.extend((value.len() as u16).to_be_bytes());
+            iim.extend(value.as_bytes());
+        }
+        let mut resources = b"8BIM\x04\x04\0\0".to_vec();
+        resources.extend((iim.len() as u32).to_be_bytes());
+        resources.extend(iim);
+        resources
+    }
+
+    #[test]
+    fn has_jpeg_location() {
+        let mut app13 = PHOTOSHOP_SIGNATURE.to_vec();
+        app13.extend(photoshop_resources(&[(2, 90, "Paris"), (2, 101, "France")]));
+        let mut data = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xED];
+        data.extend((app13.len() as u16 + 2).to_be_bytes());
+        data.extend(app13);
+        data.extend([0xFF, 0xDA, 0x00, 0x02]);
+
+        let mut gps = GPSData::default();
+        assign_location(&mut gps, &from_jpeg(&data));
+        assert_eq!(gps.city.as_deref(), Some("Paris"));
+        assert_eq!(gps.country.as_deref(), Some("France"));
+    }
+
+    #[test]
+    fn has_location_from_path() {
+        let mut app13 = PHOTOSHOP_SIGNATURE.to_vec();
+        app13.extend(photoshop_resources(&[(2, 90, "Lyon"), (2, 101, "France")]));
+        let mut segment = vec![0xFF, 0xED];
+        segment.extend((app13.len() as u16 + 2).to_be_bytes());
+        segment.extend(app13);
+        let mut data = std::fs::read(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg"),
+        )
+        .unwrap();
+        let exif_end = crate::metadata::format::jpeg_segments(&data)
+            .find(|s| s.body.starts_with(b"Exif\0\0"))
+            .unwrap()
+            .end;
+        data.splice(exif_end..exif_end, segment);
+        let root = TempDir::new();
+        let path = root.join("IMG_0001.jpg");
+        std::fs::write(&path, &data).unwrap();
+
+        let meta = crate::metadata::Metadata::from_path(&path).unwrap();
+        assert_eq!(meta.gps.city.as_deref(), Some("Lyon"));
+        assert_eq!(meta.gps.country.as_deref(), Some("France"));
+        let scanned = crate::scan::scan_directory(&root, false).unwrap();
+        assert_eq!(scanned.into_iter().next().unwrap().unwrap(), meta);
+    }
+
+    #[test]
+    fn has_png_raw_profile() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img/text_car_animal_no-gps.png");
+        let chunks = crate::metadata::png::read_text_chunks(&std::fs::read(path).unwrap());
+        let datasets = from_png_text_chunks(&chunks);
+        // Date created
+        assert!(datasets.contains(&(2, 55, b"20241227".to_vec())));
+
+        let mut gps = GPSData::default();
+        assign_location(&mut gps, &datasets);
+        assert_eq!((gps.city, gps.country), (None, None));
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use serde::de::Error;
+use serde_json::{Map, Value};
+
+use crate::error::CoreError;
+use crate::metadata::Metadata;
+
+/// Version of the JSON form of `Metadata`, increased on incompatible changes.
+///
+/// - 0: no version field, `basics.description` was named `desciption`
+/// - 1: sidecar `{"version": 1, "metadata": {...}}`
+/// - 2: the metadata fields next to `"version": 2`
+pub const SCHEMA_VERSION: u32 = 2;
+
+impl Metadata {
+    /// Serializes the metadata with the current `SCHEMA_VERSION`
+    pub fn to_json(&self) -> Result<String, CoreError> {
+        let mut value = serde_json::to_value(self)?;
+        if let Value::Object(fields) = &mut value {
+            fields.insert("version".to_string(), SCHEMA_VERSION.into());
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// Deserializes metadata written with any known schema version, migrating
+    /// the older ones. Newer versions are an `UnsupportedSchema` error.
+    pub fn from_json(json: &str) -> Result<Metadata, CoreError> {
+        let Value::Object(mut fields) = serde_json::from_str(json)? else {
+            return Ok(serde_json::from_str(json)?);
+        };
+        let version = match fields.remove("version") {
+            None => 0,
+            Some(v) => v
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| serde_json::Error::custom("Invalid schema version"))?,
+        };
+        let fields = match version {
+            0 => rename_description(fields),
+            1 => match fields.remove("metadata") {
+                Some(Value::Object(metadata)) => metadata,
+                _ => fields,
+            },
+            SCHEMA_VERSION => fields,
+            _ => return Err(CoreError::UnsupportedSchema(version)),
+        };
+        Ok(serde_json::from_value(Value::Object(fields))?)
+    }
+}
+
+fn rename_description(mut fields: Map<String, Value>) -> Map<String, Value> {
+    if let Some(Value::Object(basics)) = fields.get_mut("basics")
+        && let Some(description) = basics.remove("desciption")
+    {
+        basics.insert("description".to_string(), description);
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::diff;
+    use std::path::Path;
+
+    fn get_metadata() -> Metadata {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        let mut meta = Metadata::from_path(path).unwrap();
+        meta.basics.description = Some("Lyon".to_string());
+        meta
+    }
+
+    #[test]
+    fn has_json_round_trip() {
+        let meta = get_metadata();
+        let json = meta.to_json().unwrap();
+        assert!(json.contains("\"version\": 2"));
+        assert!(diff(&meta, &Metadata::from_json(&json).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn has_migrated_versions() {
+        let meta = get_metadata();
+        let mut current = serde_json::to_value(&meta).unwrap();
+
+        let version_1 = serde_json::json!({"version": 1, "metadata": current.clone()});
+        let loaded = Metadata::from_json(&version_1.to_string()).unwrap();
+        assert!(diff(&meta, &loaded).is_empty());
+
+        let basics = current["basics"].as_object_mut().unwrap();
+        let description = basics.remove("description").unwrap();
+        basics.insert("desciption".to_string(), description);
+        let loaded = Metadata::from_json(&current.to_string()).unwrap();
+        assert_eq!(loaded.basics.description.as_deref(), Some("Lyon"));
+    }
+
+    #[test]
+    fn has_unsupported_schema() {
+        let json = r#"{"version": 3, "file_path": ""}"#;
+        assert!(matches!(
+            Metadata::from_json(json),
+            Err(CoreError::UnsupportedSchema(3))
+        ));
+    }
+}
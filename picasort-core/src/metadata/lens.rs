@@ -0,0 +1,329 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use little_exif::{endian::Endian, exif_tag::ExifTag, metadata::Metadata};
+
+use crate::metadata::exif::ExifExtractable;
+
+/// Lens names by maker note id, "brand,id,name"
+const BUNDLED_LENSES: &str = include_str!("../../data/lenses.csv");
+
+static LENSES: LazyLock<HashMap<(&str, &str), &str>> = LazyLock::new(|| {
+    BUNDLED_LENSES
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ',');
+            Some(((parts.next()?, parts.next()?), parts.next()?))
+        })
+        .collect()
+});
+
+/// Index of the LensType in the Canon CameraSettings values
+const CANON_LENS_TYPE_INDEX: usize = 22;
+/// LensType of a Canon camera without lens information
+const CANON_NO_LENS: u16 = 0xFFFF;
+
+/// Name of the lens from the maker note of Canon and Nikon cameras, else the
+/// EXIF LensModel. Best effort: a Nikon lens missing from the bundled table,
+/// or with encrypted lens data, is described by its focal lengths and
+/// apertures.
+pub fn maker_lens(metadata: &Metadata) -> Option<String> {
+    maker_note_lens(metadata).or_else(|| {
+        String::extract(&ExifTag::LensModel(String::new()), metadata)
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty())
+    })
+}
+
+fn maker_note_lens(metadata: &Metadata) -> Option<String> {
+    let make = String::extract(&ExifTag::Make(String::new()), metadata)?.to_ascii_lowercase();
+    let endian = metadata.get_endian();
+    let note = metadata
+        .get_tag(&ExifTag::MakerNote(Vec::new()))
+        .next()?
+        .value_as_u8_vec(&endian);
+    if make.starts_with("canon") {
+        let lens_type = canon_lens_type(&note, endian == Endian::Big)?;
+        lookup("canon", &lens_type.to_string())
+    } else if make.starts_with("nikon") {
+        nikon_lens(&note)
+    } else {
+        None
+    }
+}
+
+fn lookup(brand: &str, id: &str) -> Option<String> {
+    LENSES.get(&(brand, id)).map(|name| name.to_string())
+}
+
+/// Entry of a TIFF image file directory
+struct Entry {
+    tag: u16,
+    format: u16,
+    count: u32,
+    /// Value when it fits in 4 bytes, else its offset
+    raw: u32,
+    /// Position of the raw value in the data
+    position: usize,
+}
+
+impl Entry {
+    fn size(&self) -> Option<usize> {
+        let unit: usize = match self.format {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 => 8,
+            _ => return None,
+        };
+        unit.checked_mul(self.count as usize)
+    }
+
+    /// Bytes of the value, offsets being relative to `base` in `data`
+    fn value<'d>(&self, data: &'d [u8], base: i64) -> Option<&'d [u8]> {
+        let size = self.size()?;
+        let start = if size <= 4 {
+            self.position
+        } else {
+            usize::try_from(self.raw as i64 - base).ok()?
+        };
+        data.get(start..start.checked_add(size)?)
+    }
+}
+
+fn read_u16(data: &[u8], pos: usize, big_endian: bool) -> Option<u16> {
+    let bytes = [*data.get(pos)?, *data.get(pos + 1)?];
+    Some(if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    })
+}
+
+fn read_u32(data: &[u8], pos: usize, big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(pos..pos + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+fn read_ifd(data: &[u8], start: usize, big_endian: bool) -> Option<Vec<Entry>> {
+    let count = read_u16(data, start, big_endian)? as usize;
+    (0..count)
+        .map(|i| {
+            let pos = start + 2 + 12 * i;
+            Some(Entry {
+                tag: read_u16(data, pos, big_endian)?,
+                format: read_u16(data, pos + 2, big_endian)?,
+                count: read_u32(data, pos + 4, big_endian)?,
+                raw: read_u32(data, pos + 8, big_endian)?,
+                position: pos + 8,
+            })
+        })
+        .collect()
+}
+
+/// LensType of a Canon maker note, an IFD in the byte order of the file.
+/// Its offsets are relative to the TIFF header, which is not part of the
+/// note: the values are assumed to follow the IFD, as Canon writes them.
+fn canon_lens_type(note: &[u8], big_endian: bool) -> Option<u16> {
+    let entries = read_ifd(note, 0, big_endian)?;
+    let data_start = 2 + 12 * entries.len() as i64 + 4;
+    let first_offset = entries
+        .iter()
+        .filter(|e| e.size().is_some_and(|size| size > 4))
+        .map(|e| e.raw as i64)
+        .min()?;
+    let base = first_offset - data_start;
+    let settings = entries.iter().find(|e| e.tag == 0x0001 && e.format == 3)?;
+    let values = settings.value(note, base)?;
+    let lens_type = read_u16(values, CANON_LENS_TYPE_INDEX * 2, big_endian)?;
+    (lens_type != CANON_NO_LENS).then_some(lens_type)
+}
+
+/// Lens of a Nikon type 3 maker note: "Nikon\0", a version, then a TIFF
+/// header that its offsets are relative to
+fn nikon_lens(note: &[u8]) -> Option<String> {
+    let tiff = note.strip_prefix(b"Nikon\0")?.get(4..)?;
+    let big_endian = match tiff.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let entries = read_ifd(tiff, read_u32(tiff, 4, big_endian)? as usize, big_endian)?;
+    let value = |tag: u16| entries.iter().find(|e| e.tag == tag)?.value(tiff, 0);
+
+    let lens_type = value(0x0083).and_then(|v| v.first().copied());
+    if let Some(id) = value(0x0098).and_then(nikon_lens_id) {
+        let mut key = id.to_vec();
+        key.push(lens_type.unwrap_or(0));
+        let key: Vec<String> = key.iter().map(|b| format!("{b:02X}")).collect();
+        return lookup("nikon", &key.join(" ")).or_else(|| {
+            // Focal lengths as 5 * 2^(v / 24) mm, apertures as f/2^(v / 24)
+            let focal = |v: u8| 5.0 * 2f64.powf(v as f64 / 24.0);
+            let aperture = |v: u8| 2f64.powf(v as f64 / 24.0);
+            Some(describe(
+                (focal(id[2]), focal(id[3])),
+                (aperture(id[4]), aperture(id[5])),
+            ))
+        });
+    }
+
+    // Lens: min and max focal lengths, apertures at min and max focal length
+    let lens = value(0x0084)?;
+    let rational = |i: usize| {
+        let num = read_u32(lens, 8 * i, big_endian)?;
+        let den = read_u32(lens, 8 * i + 4, big_endian)?;
+        (den != 0).then(|| num as f64 / den as f64)
+    };
+    Some(describe(
+        (rational(0)?, rational(1)?),
+        (rational(2)?, rational(3)?),
+    ))
+}
+
+/// LensIDNumber, LensFStops, MinFocalLength, MaxFocalLength,
+/// MaxApertureAtMinFocal, MaxApertureAtMaxFocal and MCUVersion of the Nikon
+/// LensData. None for the encrypted versions.
+fn nikon_lens_id(lens_data: &[u8]) -> Option<&[u8]> {
+    let start = match lens_data.get(..4)? {
+        b"0100" => 6,
+        b"0101" => 11,
+        _ => return None,
+    };
+    lens_data.get(start..start + 7)
+}
+
+/// "18-55mm f/3.5-5.6" or "50mm f/1.8"
+fn describe(focal: (f64, f64), aperture: (f64, f64)) -> String {
+    let aperture_str = |a: f64| {
+        let s = format!("{a:.1}");
+        s.strip_suffix(".0").map(str::to_string).unwrap_or(s)
+    };
+    let (focal_min, focal_max) = (focal.0.round(), focal.1.round());
+    let (aperture_min, aperture_max) = (aperture_str(aperture.0), aperture_str(aperture.1));
+    let focal = if focal_min == focal_max {
+        format!("{focal_min}mm")
+    } else {
+        format!("{focal_min}-{focal_max}mm")
+    };
+    if aperture_min == aperture_max {
+        format!("{focal} f/{aperture_min}")
+    } else {
+        format!("{focal} f/{aperture_min}-{aperture_max}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn metadata(make: &str, note: Vec<u8>) -> Metadata {
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::Make(make.to_string()));
+        metadata.set_tag(ExifTag::MakerNote(note));
+        metadata
+    }
+
+    /// Little endian Canon note with the CameraSettings values after the IFD,
+    /// at `offset` from the TIFF header
+    fn canon_note(lens_type: u16, offset: u32) -> Vec<u8> {
+        let mut note = vec![1, 0];
+        note.extend_from_slice(&1u16.to_le_bytes());
+        note.extend_from_slice(&3u16.to_le_bytes());
+        note.extend_from_slice(&30u32.to_le_bytes());
+        note.extend_from_slice(&offset.to_le_bytes());
+        note.extend_from_slice(&[0; 4]);
+        let mut settings = [0u16; 30];
+        settings[CANON_LENS_TYPE_INDEX] = lens_type;
+        note.extend(settings.iter().flat_map(|v| v.to_le_bytes()));
+        note
+    }
+
+    /// Big endian Nikon type 3 note with a LensType and a LensData
+    fn nikon_note(lens_data: &[u8]) -> Vec<u8> {
+        let mut note = b"Nikon\0\x02\x10\0\0MM\0\x2A\0\0\0\x08".to_vec();
+        note.extend_from_slice(&[0, 2]);
+        note.extend_from_slice(&[0, 0x83, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0]);
+        note.extend_from_slice(&[0, 0x98, 0, 7]);
+        note.extend_from_slice(&(lens_data.len() as u32).to_be_bytes());
+        note.extend_from_slice(&38u32.to_be_bytes());
+        note.extend_from_slice(&[0; 4]);
+        note.extend_from_slice(lens_data);
+        note
+    }
+
+    #[test]
+    fn has_canon_lens() {
+        assert_eq!(
+            maker_lens(&metadata("Canon", canon_note(237, 0x0400))).as_deref(),
+            Some("Canon EF 24-105mm f/4L IS USM")
+        );
+        assert_eq!(
+            maker_lens(&metadata("Canon", canon_note(CANON_NO_LENS, 0x0400))),
+            None
+        );
+
+        let mut unknown = metadata("Canon", canon_note(9999, 0x0400));
+        assert_eq!(maker_lens(&unknown), None);
+        unknown.set_tag(ExifTag::LensModel(
+            "EF100-400mm f/4.5-5.6L IS II USM".to_string(),
+        ));
+        assert_eq!(
+            maker_lens(&unknown).as_deref(),
+            Some("EF100-400mm f/4.5-5.6L IS II USM")
+        );
+    }
+
+    #[test]
+    fn has_nikon_lens() {
+        let known = b"0100\0\0\x01\x58\x50\x50\x14\x14\x02";
+        assert_eq!(
+            maker_lens(&metadata("NIKON CORPORATION", nikon_note(known))).as_deref(),
+            Some("AF Nikkor 50mm f/1.8")
+        );
+        // 28-50mm f/2.8-4, missing from the table
+        let unknown = b"0101\0\0\0\0\0\0\0\x7F\x00\x3C\x50\x24\x30\x00";
+        assert_eq!(
+            maker_lens(&metadata("NIKON CORPORATION", nikon_note(unknown))).as_deref(),
+            Some("28-50mm f/2.8-4")
+        );
+        let encrypted = b"0204\x12\x34\x56\x78\x9a\xbc\xde\xf0\x12\x34\x56\x78";
+        assert_eq!(
+            maker_lens(&metadata("NIKON CORPORATION", nikon_note(encrypted))),
+            None
+        );
+    }
+
+    #[test]
+    fn has_lens_model_fallback() {
+        let mut garbage = metadata("Canon", vec![0xFF; 3]);
+        garbage.set_tag(ExifTag::LensModel("RF24-70mm F2.8 L IS USM".to_string()));
+        assert_eq!(
+            maker_lens(&garbage).as_deref(),
+            Some("RF24-70mm F2.8 L IS USM")
+        );
+
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        let metadata = Metadata::new_from_path(&path).unwrap();
+        assert_eq!(
+            maker_lens(&metadata).as_deref(),
+            Some("iPhone 12 Pro back camera 4.2mm f/1.6")
+        );
+    }
+
+    #[test]
+    fn has_lens_description() {
+        assert_eq!(describe((50.4, 50.4), (1.78, 1.78)), "50mm f/1.8");
+        assert_eq!(describe((24.0, 70.0), (2.8, 2.8)), "24-70mm f/2.8");
+        assert_eq!(describe((70.0, 200.0), (4.0, 5.6)), "70-200mm f/4-5.6");
+    }
+}
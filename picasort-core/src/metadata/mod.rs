@@ -1,7 +1,689 @@
 // Copyright (c) 2025 Lemur-Catta.org
 // Author: Sylvain Gubian <sgubian@lemur-catta.org>
 
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::TimeDelta;
+
+use crate::{
+    DynamicGetSet,
+    error::CoreError,
+    metadata::{
+        basics::{Basics, Orientation, is_plausible_date},
+        exposure::Exposure,
+        format::{ImageFormat, unsupported_format_name},
+        gps::{GPSData, NULL_ISLAND_EPSILON},
+    },
+    utils::sha::{get_file_uuid, hash_bytes},
+};
+
+pub mod annotations;
 pub mod basics;
-mod camera;
+pub mod diff;
 pub mod exif;
+pub mod export;
+pub mod exposure;
+pub mod format;
 pub mod gps;
+#[cfg(feature = "gpx")]
+pub mod gpx;
+pub mod iptc;
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "lenses")]
+pub mod lens;
+pub mod pipeline;
+pub mod png;
+#[cfg(feature = "serde")]
+pub mod sidecar;
+pub mod tags;
+pub mod xmp;
+
+pub use annotations::{Annotations, ColorLabel, read_annotations};
+pub use basics::earliest_date;
+pub use diff::{FieldDiff, diff};
+pub use exif::exif_hash;
+pub use export::{csv_header, to_geojson};
+pub use gps::{apply_gps, gps_only};
+#[cfg(feature = "gpx")]
+pub use gpx::{GpxTrack, geotag_from_gpx};
+#[cfg(feature = "lenses")]
+pub use lens::maker_lens;
+pub use pipeline::MetadataPipeline;
+#[cfg(feature = "serde")]
+pub use sidecar::read_sidecar;
+
+/// Ratio of the long side to the short side from which
+/// `Metadata::is_panorama` considers an image a panorama
+pub const PANORAMA_ASPECT_RATIO: f64 = 2.5;
+
+/// XMP namespace of the Google photo sphere and panorama properties (GPano)
+const GPANO_NAMESPACE: &str = "http://ns.google.com/photos/1.0/panorama/";
+
+/// Relative difference between the EXIF and actual dimensions over which
+/// `Metadata::likely_edited` considers an image edited
+#[cfg(feature = "pixels")]
+pub const EDITED_DIMENSION_TOLERANCE: f64 = 0.01;
+
+/// All the metadata extracted from a single image file
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metadata {
+    pub file_path: PathBuf,
+    pub uuid: String,
+    pub basics: Basics,
+    pub gps: GPSData,
+    pub exposure: Exposure,
+    /// The embedded XMP uses the GPano namespace written by phones and
+    /// stitching software for panoramas and photo spheres
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gpano: bool,
+}
+
+impl Metadata {
+    /// Reads the file EXIF and content hash and assigns all the known structs
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Metadata, CoreError> {
+        let path = path.as_ref();
+        let exif = little_exif::metadata::Metadata::new_from_path(path)?;
+        let mut meta = Metadata::from_exif(path.to_path_buf(), get_file_uuid(path)?, &exif)?;
+        meta.assign_header(&xmp::read_header(path)?);
+        Ok(meta)
+    }
+
+    /// Reads the metadata of an image held in memory, `hint_name` is used as
+    /// its file path. The uuid is the hash of `data`. A file of another
+    /// format gives a `CoreError::UnsupportedFormat` with the name of the
+    /// detected format, else of the extension of `hint_name`.
+    pub fn from_bytes(data: &[u8], hint_name: Option<&str>) -> Result<Metadata, CoreError> {
+        let file_path = PathBuf::from(hint_name.unwrap_or_default());
+        let format = ImageFormat::detect(data).ok_or_else(|| {
+            let name = unsupported_format_name(data)
+                .map(str::to_string)
+                .or_else(|| {
+                    let extension = file_path.extension()?.to_str()?;
+                    Some(extension.to_ascii_uppercase())
+                })
+                .unwrap_or_else(|| "unknown".to_string());
+            CoreError::UnsupportedFormat(name)
+        })?;
+        // little_exif takes a Vec, the buffer has to be copied
+        let exif =
+            little_exif::metadata::Metadata::new_from_vec(&data.to_vec(), format.file_extension())?;
+        let mut meta = Metadata::from_exif(file_path, hash_bytes(data), &exif)?;
+        meta.assign_xmp(xmp::header_xmp_packet(data));
+        let iptc = match format {
+            ImageFormat::Png => {
+                let chunks = png::read_text_chunks(data);
+                png::assign_text_chunks(&mut meta.basics, &chunks);
+                iptc::from_png_text_chunks(&chunks)
+            }
+            ImageFormat::Jpeg => iptc::from_jpeg(data),
+            _ => Vec::new(),
+        };
+        iptc::assign_location(&mut meta.gps, &iptc);
+        Ok(meta)
+    }
+
+    pub(crate) fn from_exif(
+        file_path: PathBuf,
+        uuid: String,
+        exif: &little_exif::metadata::Metadata,
+    ) -> Result<Metadata, CoreError> {
+        let mut meta = Metadata {
+            file_path,
+            uuid,
+            ..Default::default()
+        };
+        MetadataPipeline::new()
+            .with(&mut meta.basics)
+            .with(&mut meta.gps)
+            .with(&mut meta.exposure)
+            .run(exif)
+            .into_iter()
+            .collect::<Result<(), _>>()
+            .map_err(|e| CoreError::InvalidEXIFConversion(e.to_string()))?;
+        Ok(meta)
+    }
+
+    /// Assigns the fields read besides the EXIF from the first
+    /// `XMP_SEARCH_SIZE` bytes of the file: the XMP ones, and the IPTC city
+    /// and country of a JPEG or a PNG
+    pub(crate) fn assign_header(&mut self, header: &[u8]) {
+        self.assign_xmp(xmp::header_xmp_packet(header));
+        let iptc = match ImageFormat::detect(header) {
+            Some(ImageFormat::Png) => iptc::from_png_text_chunks(&png::read_text_chunks(header)),
+            Some(ImageFormat::Jpeg) => iptc::from_jpeg(header),
+            _ => Vec::new(),
+        };
+        iptc::assign_location(&mut self.gps, &iptc);
+    }
+
+    /// Assigns the fields read from the XMP packet embedded in the file
+    fn assign_xmp(&mut self, xmp: Option<&str>) {
+        self.gpano = xmp.is_some_and(|xmp| xmp.contains(GPANO_NAMESPACE));
+    }
+
+    /// Compares the metadata without the fields changing whenever the file is
+    /// touched: the path, the uuid (the hash of the whole file, EXIF
+    /// included) and the modification date and offset
+    pub fn content_eq(&self, other: &Metadata) -> bool {
+        let stable = |basics: &Basics| Basics {
+            modification_date: None,
+            modification_offset: None,
+            ..basics.clone()
+        };
+        stable(&self.basics) == stable(&other.basics)
+            && self.gps == other.gps
+            && self.exposure == other.exposure
+            && self.gpano == other.gpano
+    }
+
+    /// Tells if the image was likely cropped or resized after capture: its
+    /// EXIF dimensions differ from the ones of the encoded image by more than
+    /// `EDITED_DIMENSION_TOLERANCE`. None when the EXIF dimensions are missing
+    /// or the file cannot be read.
+    #[cfg(feature = "pixels")]
+    pub fn likely_edited(&self) -> Option<bool> {
+        let (width, height) = (self.basics.width?, self.basics.height?);
+        let data = fs::read(&self.file_path).ok()?;
+        let (actual_width, actual_height) = crate::utils::pixels::image_dimensions(&data).ok()?;
+        let differs = |exif: usize, actual: u32| {
+            let actual = actual as f64;
+            (exif as f64 - actual).abs() > actual * EDITED_DIMENSION_TOLERANCE
+        };
+        Some(differs(width, actual_width) || differs(height, actual_height))
+    }
+
+    /// Heuristic check that the orientation tag matches the stored pixels.
+    /// Camera sensors are landscape: a tag rotating by 90° on pixels stored
+    /// as portrait means they were likely already rotated, and the image
+    /// would be displayed sideways. None when the orientation is missing or
+    /// unknown, or the image is square or without dimensions.
+    pub fn orientation_consistent(&self) -> Option<bool> {
+        let orientation = self
+            .basics
+            .orientation
+            .filter(|o| *o != Orientation::Unknown)?;
+        let (width, height) = (self.basics.width?, self.basics.height?);
+        if width == height {
+            return None;
+        }
+        Some(!orientation.swaps_dimensions() || width > height)
+    }
+
+    /// Tells if the image is a panorama: its displayed long side is at least
+    /// `PANORAMA_ASPECT_RATIO` times its short side, or its XMP uses the
+    /// GPano namespace, see `gpano`.
+    pub fn is_panorama(&self) -> bool {
+        if let Some((width, height)) = self.basics.display_dimensions() {
+            let (long, short) = (width.max(height) as f64, width.min(height) as f64);
+            if short > 0.0 && long / short >= PANORAMA_ASPECT_RATIO {
+                return true;
+            }
+        }
+        self.gpano
+    }
+
+    /// Key sorting photos by best date, undated last, then by lowercased file
+    /// stem: (best date as Unix seconds or `i64::MAX`, stem)
+    pub fn sort_key(&self) -> (i64, String) {
+        let timestamp = self
+            .basics
+            .best_date()
+            .map_or(i64::MAX, |date| date.timestamp());
+        let stem = self
+            .file_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        (timestamp, stem)
+    }
+
+    /// Drift of the camera clock, GPS UTC time minus the original date. The
+    /// original date is converted with its offset when known, else taken as
+    /// UTC. Negative for a camera clock ahead, so that it can be given as is
+    /// to `Basics::shift`. None without both timestamps.
+    pub fn clock_drift(&self) -> Option<TimeDelta> {
+        let gps = self.gps.datetime_utc()?;
+        let original = self.basics.original_date?;
+        let original = match self.basics.original_offset {
+            Some(offset) => original
+                .naive_utc()
+                .and_local_timezone(offset)
+                .single()?
+                .to_utc(),
+            None => original,
+        };
+        Some(gps - original)
+    }
+
+    /// Audits the metadata: missing dimensions or date, zero dimensions,
+    /// impossible dates and GPS data present but invalid or at the null island
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut push = |field, problem| issues.push(ValidationIssue { field, problem });
+
+        for (field, value) in [("width", self.basics.width), ("height", self.basics.height)] {
+            match value {
+                None => push(field, Problem::Missing),
+                Some(0) => push(field, Problem::Zero),
+                Some(_) => (),
+            }
+        }
+        if self.basics.best_date().is_none() {
+            push("date", Problem::Missing);
+        }
+        for (field, date) in [
+            ("creation_date", &self.basics.creation_date),
+            ("original_date", &self.basics.original_date),
+            ("modification_date", &self.basics.modification_date),
+        ] {
+            if let Some(date) = date
+                && !is_plausible_date(date)
+            {
+                push(field, Problem::ImpossibleDate);
+            }
+        }
+        // A place name without a position is not a broken position
+        let gps = &self.gps;
+        let has_gps = gps.latitude_ref.is_some()
+            || gps.latitude.is_some()
+            || gps.longitude_ref.is_some()
+            || gps.longitude.is_some();
+        if has_gps && !self.gps.is_plausible(NULL_ISLAND_EPSILON) {
+            push("gps", Problem::Invalid);
+        }
+        issues
+    }
+}
+
+/// Reads the metadata of an image with the reader of its format, detected from
+/// the file content rather than its extension
+pub fn extract_any<P: AsRef<Path>>(path: P) -> Result<Metadata, CoreError> {
+    let path = path.as_ref();
+    let data = fs::read(path)?;
+    let mut meta = Metadata::from_bytes(&data, path.to_str())?;
+    meta.file_path = path.to_path_buf();
+    Ok(meta)
+}
+
+/// Kind of problem found by `Metadata::validate`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Problem {
+    Missing,
+    Invalid,
+    Zero,
+    ImpossibleDate,
+}
+
+/// A problem found on a field of a `Metadata`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    pub field: &'static str,
+    pub problem: Problem,
+}
+
+/// What identifies two images as duplicates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicateKey {
+    /// Same file content hash
+    ContentHash,
+    /// Same EXIF ImageUniqueID, surviving re-encoding of in-camera duplicates
+    ImageUniqueId,
+}
+
+/// Groups the indices of `items` sharing the same `key`. Only groups of at
+/// least two items are returned, items without the key are ignored.
+pub fn find_duplicates(items: &[Metadata], key: DuplicateKey) -> Vec<Vec<usize>> {
+    let mut groups: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, meta) in items.iter().enumerate() {
+        let value = match key {
+            DuplicateKey::ContentHash => Some(meta.uuid.as_str()).filter(|u| !u.is_empty()),
+            DuplicateKey::ImageUniqueId => meta.basics.image_unique_id.as_deref(),
+        };
+        if let Some(value) = value {
+            groups.entry(value).or_default().push(index);
+        }
+    }
+    let mut duplicates: Vec<Vec<usize>> = groups.into_values().filter(|g| g.len() > 1).collect();
+    duplicates.sort();
+    duplicates
+}
+
+/// Fraction of `items` having each field of `Basics` and `GPSData` populated.
+/// The map is empty when there is no item.
+pub fn tag_coverage(items: &[Metadata]) -> HashMap<&'static str, f64> {
+    let mut coverage = HashMap::new();
+    if items.is_empty() {
+        return coverage;
+    }
+    for name in Basics::get_field_names() {
+        let count = items
+            .iter()
+            .filter(|m| m.basics.get_value_by_field_name(name).is_some())
+            .count();
+        coverage.insert(name, count as f64 / items.len() as f64);
+    }
+    for name in GPSData::get_field_names() {
+        let count = items
+            .iter()
+            .filter(|m| m.gps.get_value_by_field_name(name).is_some())
+            .count();
+        coverage.insert(name, count as f64 / items.len() as f64);
+    }
+    coverage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::exif::ExifAssignable;
+    use crate::test_utils::TempDir;
+    use std::path::Path;
+
+    fn get_image_path(filename: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img")
+            .join(filename)
+    }
+
+    #[test]
+    fn has_sort_key() {
+        let meta = |path: &str, date: Option<&str>| {
+            let mut meta = Metadata {
+                file_path: PathBuf::from(path),
+                ..Default::default()
+            };
+            meta.basics.original_date =
+                date.map(|d| chrono::DateTime::parse_from_rfc3339(d).unwrap().to_utc());
+            meta
+        };
+        let mut items = [
+            meta("/b/undated.jpg", None),
+            meta("/a/IMG_0002.JPG", Some("2024-10-28T20:35:03Z")),
+            meta("/b/img_0001.jpg", Some("2024-10-28T20:35:03Z")),
+            meta("/a/Aardvark.png", None),
+            meta("/c/old.jpg", Some("1999-01-01T00:00:00Z")),
+        ];
+        assert_eq!(items[1].sort_key(), (1_730_147_703, "img_0002".to_string()));
+        assert_eq!(items[0].sort_key(), (i64::MAX, "undated".to_string()));
+        items.sort_by_key(Metadata::sort_key);
+        let paths: Vec<_> = items
+            .iter()
+            .map(|m| m.file_path.to_str().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/c/old.jpg",
+                "/b/img_0001.jpg",
+                "/a/IMG_0002.JPG",
+                "/a/Aardvark.png",
+                "/b/undated.jpg",
+            ]
+        );
+    }
+
+    #[test]
+    fn has_clock_drift() {
+        let mut meta = Metadata::from_path(get_image_path("text_icon_gps.jpg")).unwrap();
+        // 2024-10-28 20:35:03.383 +01:00 by the camera, 2024-10-29 11:33:25 UTC by GPS
+        assert_eq!(
+            meta.clock_drift().map(|d| d.num_milliseconds()),
+            Some(57_501_617)
+        );
+        meta.basics.original_offset = None;
+        assert_eq!(
+            meta.clock_drift().map(|d| d.num_milliseconds()),
+            Some(53_901_617)
+        );
+        meta.gps.time = None;
+        assert_eq!(meta.clock_drift(), None);
+
+        let meta = Metadata::from_path(get_image_path("text_car_animal_no-gps.png")).unwrap();
+        assert_eq!(meta.clock_drift(), None);
+    }
+
+    #[rstest::rstest]
+    #[case(Some(Orientation::Rotated90DegCW), 3840, 2160, Some(true))]
+    #[case(Some(Orientation::Rotated90DegCW), 2160, 3840, Some(false))]
+    #[case(Some(Orientation::Rotated90DegCCW), 2160, 3840, Some(false))]
+    #[case(Some(Orientation::Normal), 2160, 3840, Some(true))]
+    #[case(Some(Orientation::Rotated180Deg), 3840, 2160, Some(true))]
+    #[case(Some(Orientation::Rotated90DegCW), 1000, 1000, None)]
+    #[case(Some(Orientation::Unknown), 3840, 2160, None)]
+    #[case(None, 3840, 2160, None)]
+    fn has_orientation_consistency(
+        #[case] orientation: Option<Orientation>,
+        #[case] width: usize,
+        #[case] height: usize,
+        #[case] expected: Option<bool>,
+    ) {
+        let mut meta = Metadata::default();
+        meta.basics.orientation = orientation;
+        meta.basics.width = Some(width);
+        meta.basics.height = Some(height);
+        assert_eq!(meta.orientation_consistent(), expected);
+    }
+
+    #[rstest::rstest]
+    #[case(Some((8000, 3000)), None, true)]
+    #[case(Some((3000, 8000)), Some(Orientation::Rotated90DegCW), true)]
+    #[case(Some((5000, 2000)), None, true)]
+    #[case(Some((4000, 3000)), None, false)]
+    #[case(Some((0, 3000)), None, false)]
+    #[case(None, None, false)]
+    fn has_panorama_by_ratio(
+        #[case] dimensions: Option<(usize, usize)>,
+        #[case] orientation: Option<Orientation>,
+        #[case] expected: bool,
+    ) {
+        let mut meta = Metadata::default();
+        meta.basics.width = dimensions.map(|(w, _)| w);
+        meta.basics.height = dimensions.map(|(_, h)| h);
+        meta.basics.orientation = orientation;
+        assert_eq!(meta.is_panorama(), expected);
+    }
+
+    #[test]
+    fn has_panorama_by_xmp() {
+        let root = TempDir::new();
+        let path = root.join("PANO_0001.jpg");
+        let xmp = b"http://ns.adobe.com/xap/1.0/\0<x:xmpmeta><rdf:Description \
+            xmlns:GPano=\"http://ns.google.com/photos/1.0/panorama/\"/></x:xmpmeta>";
+        let mut data = fs::read(get_image_path("text_icon_gps.jpg")).unwrap();
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend_from_slice(&(xmp.len() as u16 + 2).to_be_bytes());
+        segment.extend_from_slice(xmp);
+        // little_exif wants the EXIF segment first
+        let exif_end = format::jpeg_segments(&data)
+            .find(|s| s.body.starts_with(b"Exif\0\0"))
+            .unwrap()
+            .end;
+        data.splice(exif_end..exif_end, segment);
+        fs::write(&path, &data).unwrap();
+        assert!(Metadata::from_bytes(&data, None).unwrap().gpano);
+        let mut meta = Metadata::from_path(&path).unwrap();
+        assert!(meta.gpano);
+        meta.basics.width = Some(4000);
+        meta.basics.height = Some(3000);
+        assert!(meta.is_panorama());
+
+        let meta = Metadata::from_path(get_image_path("text_icon_gps.jpg")).unwrap();
+        assert!(!meta.is_panorama());
+    }
+
+    #[cfg(feature = "pixels")]
+    #[test]
+    fn has_likely_edited() {
+        for filename in ["text_icon_gps.jpg", "text_car_animal_no-gps.png"] {
+            let meta = Metadata::from_path(get_image_path(filename)).unwrap();
+            assert_eq!(meta.likely_edited(), Some(false));
+        }
+        let mut meta = Metadata::from_path(get_image_path("text_icon_gps.jpg")).unwrap();
+        meta.basics.width = Some(3850);
+        assert_eq!(meta.likely_edited(), Some(false));
+        meta.basics.width = Some(4032);
+        assert_eq!(meta.likely_edited(), Some(true));
+        meta.basics.height = None;
+        assert_eq!(meta.likely_edited(), None);
+        meta.file_path = PathBuf::from("missing.jpg");
+        meta.basics.height = Some(2160);
+        assert_eq!(meta.likely_edited(), None);
+    }
+
+    #[test]
+    fn has_content_eq() {
+        let root = TempDir::new();
+        let path = root.join("touched.jpg");
+        fs::copy(get_image_path("text_icon_gps.jpg"), &path).unwrap();
+        let mut exif = little_exif::metadata::Metadata::new_from_path(&path).unwrap();
+        exif.set_tag(little_exif::exif_tag::ExifTag::ModifyDate(
+            "2025:01:02 03:04:05".to_string(),
+        ));
+        exif.write_to_file(&path).unwrap();
+
+        let meta = Metadata::from_path(get_image_path("text_icon_gps.jpg")).unwrap();
+        let touched = Metadata::from_path(&path).unwrap();
+        assert_ne!(meta.uuid, touched.uuid);
+        assert_ne!(
+            meta.basics.modification_date,
+            touched.basics.modification_date
+        );
+        assert!(meta.content_eq(&touched));
+
+        exif.set_tag(little_exif::exif_tag::ExifTag::Artist(
+            "Jane Doe".to_string(),
+        ));
+        exif.write_to_file(&path).unwrap();
+        assert!(!meta.content_eq(&Metadata::from_path(&path).unwrap()));
+
+        let mut moved = meta.clone();
+        moved.gps.altitude = Some(0.0);
+        assert!(!meta.content_eq(&moved));
+    }
+
+    #[test]
+    fn has_artist() {
+        let root = TempDir::new();
+        let path = root.join("artist.jpg");
+        fs::copy(get_image_path("text_icon_gps.jpg"), &path).unwrap();
+        let mut exif = little_exif::metadata::Metadata::new_from_path(&path).unwrap();
+        exif.set_tag(little_exif::exif_tag::ExifTag::Artist(
+            "Jane Doe".to_string(),
+        ));
+        exif.write_to_file(&path).unwrap();
+
+        let meta = Metadata::from_path(&path).unwrap();
+        assert_eq!(meta.basics.artist.as_deref(), Some("Jane Doe"));
+        let original = Metadata::from_path(get_image_path("text_icon_gps.jpg")).unwrap();
+        assert_eq!(original.basics.artist, None);
+    }
+
+    #[test]
+    fn has_tag_coverage() {
+        let items = vec![
+            Metadata::from_path(get_image_path("text_car_animal_no-gps.png")).unwrap(),
+            Metadata::from_path(get_image_path("text_icon_gps.jpg")).unwrap(),
+        ];
+        let coverage = tag_coverage(&items);
+        assert_eq!(coverage["width"], 1.0);
+        assert_eq!(coverage["latitude"], 0.5);
+        assert_eq!(coverage["copyright"], 0.0);
+        assert!(tag_coverage(&[]).is_empty());
+    }
+
+    #[test]
+    fn has_duplicates() {
+        let mut items: Vec<Metadata> = (0..4).map(|_| Metadata::default()).collect();
+        items[0].uuid = "aa".to_string();
+        items[1].uuid = "bb".to_string();
+        items[2].uuid = "aa".to_string();
+        items[1].basics.image_unique_id = Some("id".to_string());
+        items[3].basics.image_unique_id = Some("id".to_string());
+
+        assert_eq!(
+            find_duplicates(&items, DuplicateKey::ContentHash),
+            vec![vec![0, 2]]
+        );
+        assert_eq!(
+            find_duplicates(&items, DuplicateKey::ImageUniqueId),
+            vec![vec![1, 3]]
+        );
+    }
+
+    #[test]
+    fn has_validation_issues() {
+        use chrono::DateTime;
+
+        let meta = Metadata::from_path(get_image_path("text_icon_gps.jpg")).unwrap();
+        assert!(meta.validate().is_empty());
+
+        let mut meta = Metadata::default();
+        meta.basics.width = Some(0);
+        meta.basics.modification_date = Some(
+            DateTime::parse_from_rfc3339("1700-01-01T00:00:00Z")
+                .unwrap()
+                .to_utc(),
+        );
+        meta.gps.latitude_ref = Some("N".to_string());
+        let issues = meta.validate();
+        let expected = [
+            ("width", Problem::Zero),
+            ("height", Problem::Missing),
+            ("modification_date", Problem::ImpossibleDate),
+            ("gps", Problem::Invalid),
+        ];
+        assert_eq!(
+            issues,
+            expected
+                .into_iter()
+                .map(|(field, problem)| ValidationIssue { field, problem })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn has_extracted_any_format() {
+        let root = TempDir::new();
+        // The extension lies, the content decides
+        let renamed = root.join("photo.png");
+        fs::copy(get_image_path("text_icon_gps.jpg"), &renamed).unwrap();
+        let unknown = root.join("photo.jpg");
+        fs::write(&unknown, b"8BPS\0\x01").unwrap();
+        let raw = root.join("photo.cr3");
+        fs::write(&raw, b"\0\0\0\x18").unwrap();
+
+        let meta = extract_any(&renamed).unwrap();
+        assert_eq!(meta.basics.make.as_deref(), Some("Apple"));
+        assert!(meta.gps.is_valid());
+        let meta = extract_any(get_image_path("text_car_animal_no-gps.png")).unwrap();
+        assert_eq!(meta.basics.make.as_deref(), Some("SONY"));
+        assert!(matches!(
+            extract_any(&unknown),
+            Err(CoreError::UnsupportedFormat(name)) if name == "PSD"
+        ));
+        assert!(matches!(
+            extract_any(&raw),
+            Err(CoreError::UnsupportedFormat(name)) if name == "CR3"
+        ));
+        assert!(matches!(
+            extract_any(root.join("missing.jpg")),
+            Err(CoreError::IO(_))
+        ));
+    }
+
+    #[test]
+    fn has_metadata_from_bytes() {
+        let path = get_image_path("text_icon_gps.jpg");
+        let data = fs::read(&path).unwrap();
+        let meta = Metadata::from_bytes(&data, Some("upload.jpg")).unwrap();
+        let from_file = Metadata::from_path(&path).unwrap();
+        assert_eq!(meta.file_path, PathBuf::from("upload.jpg"));
+        assert_eq!(meta.uuid, from_file.uuid);
+        assert!(diff(&meta, &from_file).is_empty());
+        assert!(Metadata::from_bytes(b"not an image", None).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::path::Path;
+
+use crate::{
+    error::CoreError,
+    metadata::exif::{ConverterRegistry, ExifAssignable},
+};
+
+/// Runs a list of assignables, in insertion order, against a single parsed
+/// EXIF block
+#[derive(Debug, Default)]
+pub struct MetadataPipeline<'p, 'a> {
+    steps: Vec<&'p mut dyn ExifAssignable<'a>>,
+    registry: ConverterRegistry,
+}
+
+impl<'p, 'a> MetadataPipeline<'p, 'a> {
+    pub fn new() -> MetadataPipeline<'p, 'a> {
+        MetadataPipeline::default()
+    }
+
+    /// Appends `step` to the assignables to run
+    pub fn with(mut self, step: &'p mut dyn ExifAssignable<'a>) -> MetadataPipeline<'p, 'a> {
+        self.steps.push(step);
+        self
+    }
+
+    /// Converters used by every step instead of the curated ones
+    pub fn with_registry(mut self, registry: ConverterRegistry) -> MetadataPipeline<'p, 'a> {
+        self.registry = registry;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Assigns every step from `exif`. A failing step does not stop the
+    /// others, the results are in the steps order.
+    pub fn run(&mut self, exif: &little_exif::metadata::Metadata) -> Vec<Result<(), &'static str>> {
+        self.steps
+            .iter_mut()
+            .map(|step| step.assign_with_registry(exif, &self.registry))
+            .collect()
+    }
+
+    /// Parses the EXIF of the file once and runs every step on it
+    pub fn run_path<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Vec<Result<(), &'static str>>, CoreError> {
+        let exif = little_exif::metadata::Metadata::new_from_path(path.as_ref())?;
+        Ok(self.run(&exif))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::{Metadata, basics::Basics, exposure::Exposure, gps::GPSData};
+    use std::path::PathBuf;
+
+    fn get_image_path(filename: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img")
+            .join(filename)
+    }
+
+    #[test]
+    fn has_pipeline_run() {
+        let path = get_image_path("text_icon_gps.jpg");
+        let (mut basics, mut gps, mut exposure) =
+            (Basics::default(), GPSData::default(), Exposure::default());
+        let mut pipeline = MetadataPipeline::new()
+            .with(&mut basics)
+            .with(&mut gps)
+            .with(&mut exposure);
+        assert_eq!(pipeline.len(), 3);
+        let results = pipeline.run_path(&path).unwrap();
+        assert_eq!(results, vec![Ok(()), Ok(()), Ok(())]);
+
+        let meta = Metadata::from_path(&path).unwrap();
+        assert_eq!(format!("{basics:?}"), format!("{:?}", meta.basics));
+        assert_eq!(format!("{gps:?}"), format!("{:?}", meta.gps));
+        assert_eq!(format!("{exposure:?}"), format!("{:?}", meta.exposure));
+        assert!(MetadataPipeline::new().run_path(&path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+// PNG files often keep their metadata in text chunks instead of EXIF:
+// tEXt (Latin-1), zTXt (compressed Latin-1) and iTXt (UTF-8, optionally
+// compressed), each holding a keyword and a text.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use miniz_oxide::inflate::decompress_to_vec_zlib_with_limit;
+
+use crate::metadata::basics::Basics;
+
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+/// Upper bound of a decompressed text, against decompression bombs
+const MAX_TEXT_SIZE: usize = 1 << 24;
+
+/// Reads the (keyword, text) pairs of the text chunks, in file order.
+/// Unreadable chunks are skipped, a truncated file stops the reading.
+pub fn read_text_chunks(data: &[u8]) -> Vec<(String, String)> {
+    let mut chunks = Vec::new();
+    let Some(mut rest) = data.strip_prefix(PNG_SIGNATURE) else {
+        return chunks;
+    };
+    // Chunk: length (4 bytes, big endian), type (4 bytes), data, CRC (4 bytes)
+    while let [l0, l1, l2, l3, t0, t1, t2, t3, body @ ..] = rest {
+        let length = u32::from_be_bytes([*l0, *l1, *l2, *l3]) as usize;
+        let Some(chunk) = body.get(..length) else {
+            break;
+        };
+        let text = match &[*t0, *t1, *t2, *t3] {
+            b"tEXt" => parse_text(chunk),
+            b"zTXt" => parse_compressed_text(chunk),
+            b"iTXt" => parse_international_text(chunk),
+            b"IEND" => break,
+            _ => None,
+        };
+        chunks.extend(text);
+        rest = body.get(length + 4..).unwrap_or_default();
+    }
+    chunks
+}
+
+/// Fills the empty fields of `basics` from the "Creation Time", "Description"
+/// and "Comment" texts
+pub fn assign_text_chunks(basics: &mut Basics, chunks: &[(String, String)]) {
+    for (keyword, text) in chunks {
+        match keyword.as_str() {
+            "Creation Time" if basics.creation_date.is_none() => {
+                basics.creation_date = parse_creation_time(text);
+            }
+            "Description" | "Comment" if basics.description.is_none() => {
+                basics.description = Some(text.clone());
+            }
+            _ => (),
+        }
+    }
+}
+
+/// "Creation Time" is free text, RFC 1123 being only recommended
+fn parse_creation_time(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(date) = DateTime::parse_from_rfc2822(text) {
+        return Some(date.to_utc());
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Some(date.to_utc());
+    }
+    [
+        "%Y:%m:%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .map(|date| date.and_utc())
+}
+
+fn split_keyword(chunk: &[u8]) -> Option<(String, &[u8])> {
+    let end = chunk.iter().position(|b| *b == 0)?;
+    Some((latin1(&chunk[..end]), &chunk[end + 1..]))
+}
+
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| *b as char).collect()
+}
+
+fn inflate(data: &[u8]) -> Option<Vec<u8>> {
+    decompress_to_vec_zlib_with_limit(data, MAX_TEXT_SIZE).ok()
+}
+
+fn parse_text(chunk: &[u8]) -> Option<(String, String)> {
+    let (keyword, text) = split_keyword(chunk)?;
+    Some((keyword, latin1(text)))
+}
+
+fn parse_compressed_text(chunk: &[u8]) -> Option<(String, String)> {
+    let (keyword, rest) = split_keyword(chunk)?;
+    // Compression method 0 (zlib) is the only one defined
+    let [0, compressed @ ..] = rest else {
+        return None;
+    };
+    Some((keyword, latin1(&inflate(compressed)?)))
+}
+
+fn parse_international_text(chunk: &[u8]) -> Option<(String, String)> {
+    let (keyword, rest) = split_keyword(chunk)?;
+    let [flag, 0, rest @ ..] = rest else {
+        return None;
+    };
+    // Language tag then translated keyword, both null terminated
+    let language_end = rest.iter().position(|b| *b == 0)?;
+    let rest = &rest[language_end + 1..];
+    let translated_end = rest.iter().position(|b| *b == 0)?;
+    let text = &rest[translated_end + 1..];
+    let text = match flag {
+        0 => text.to_vec(),
+        _ => inflate(text)?,
+    };
+    Some((keyword, String::from_utf8(text).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::path::Path;
+
+    fn chunk(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut bytes = (data.len() as u32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(kind);
+        bytes.extend_from_slice(data);
+        // The CRC is not checked
+        bytes.extend_from_slice(&[0; 4]);
+        bytes
+    }
+
+    fn png(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let mut bytes = PNG_SIGNATURE.to_vec();
+        chunks.iter().for_each(|c| bytes.extend_from_slice(c));
+        bytes.extend(chunk(b"IEND", &[]));
+        bytes
+    }
+
+    #[test]
+    fn has_text_chunks() {
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(b"Lyon", 6);
+        let mut ztxt = b"Location\0\0".to_vec();
+        ztxt.extend(compressed);
+        let data = png(&[
+            chunk(b"tEXt", b"Creation Time\0Tue, 29 Oct 2024 12:33:25 +0100"),
+            chunk(b"IDAT", &[1, 2, 3]),
+            chunk(b"iTXt", "Description\0\0\0fr\0Légende\0Le chat".as_bytes()),
+            chunk(b"zTXt", &ztxt),
+            chunk(b"tEXt", b"no keyword end"),
+        ]);
+        assert_eq!(
+            read_text_chunks(&data),
+            vec![
+                (
+                    "Creation Time".to_string(),
+                    "Tue, 29 Oct 2024 12:33:25 +0100".to_string()
+                ),
+                ("Description".to_string(), "Le chat".to_string()),
+                ("Location".to_string(), "Lyon".to_string()),
+            ]
+        );
+
+        let mut basics = Basics::default();
+        assign_text_chunks(&mut basics, &read_text_chunks(&data));
+        assert_eq!(
+            basics.creation_date,
+            DateTime::parse_from_rfc3339("2024-10-29T11:33:25Z")
+                .ok()
+                .map(|d| d.to_utc())
+        );
+        assert_eq!(basics.description.as_deref(), Some("Le chat"));
+    }
+
+    #[test]
+    fn has_truncated_chunks() {
+        let mut data = png(&[chunk(b"tEXt", b"Comment\0first")]);
+        data.truncate(PNG_SIGNATURE.len() + 10);
+        assert!(read_text_chunks(&data).is_empty());
+        assert!(read_text_chunks(b"GIF89a").is_empty());
+    }
+
+    #[test]
+    fn has_fixture_text_chunks() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img/text_car_animal_no-gps.png");
+        let chunks = read_text_chunks(&std::fs::read(path).unwrap());
+        let keywords: Vec<&str> = chunks.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keywords,
+            [
+                "Raw profile type exif",
+                "Raw profile type iptc",
+                "XML:com.adobe.xmp"
+            ]
+        );
+        assert!(chunks[2].1.starts_with("<?xpacket"));
+    }
+
+    #[rstest]
+    #[case("Tue, 29 Oct 2024 12:33:25 +0100", Some("2024-10-29T11:33:25Z"))]
+    #[case("2024-10-29T12:33:25+01:00", Some("2024-10-29T11:33:25Z"))]
+    #[case("2024:10:29 11:33:25", Some("2024-10-29T11:33:25Z"))]
+    #[case("2024-10-29 11:33:25", Some("2024-10-29T11:33:25Z"))]
+    #[case("last summer", None)]
+    fn has_creation_time(#[case] text: &str, #[case] expected: Option<&str>) {
+        let expected = expected.map(|e| DateTime::parse_from_rfc3339(e).unwrap().to_utc());
+        assert_eq!(parse_creation_time(text), expected);
+    }
+}
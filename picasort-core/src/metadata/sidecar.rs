@@ -0,0 +1,68 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::CoreError;
+use crate::metadata::Metadata;
+
+/// Appended to the image file name to name its sidecar
+pub const SIDECAR_EXTENSION: &str = "picasort.json";
+
+/// Path of the sidecar of an image, `photo.jpg` has `photo.jpg.picasort.json`
+pub fn sidecar_path(image: &Path) -> PathBuf {
+    let mut name = image.as_os_str().to_owned();
+    name.push(".");
+    name.push(SIDECAR_EXTENSION);
+    PathBuf::from(name)
+}
+
+impl Metadata {
+    /// Writes the metadata as JSON next to its image, returns the sidecar path
+    pub fn write_sidecar(&self) -> Result<PathBuf, CoreError> {
+        let path = sidecar_path(&self.file_path);
+        fs::write(&path, self.to_json()?)?;
+        Ok(path)
+    }
+}
+
+/// Reads the sidecar written by `Metadata::write_sidecar` for `image`
+pub fn read_sidecar(image: &Path) -> Result<Metadata, CoreError> {
+    Metadata::from_json(&fs::read_to_string(sidecar_path(image))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::diff;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn has_sidecar() {
+        let root = TempDir::new();
+        let image = root.join("photo.jpg");
+        fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg"),
+            &image,
+        )
+        .unwrap();
+
+        let meta = Metadata::from_path(&image).unwrap();
+        let path = meta.write_sidecar().unwrap();
+        assert_eq!(path, root.join("photo.jpg.picasort.json"));
+        let loaded = read_sidecar(&image).unwrap();
+        assert_eq!(loaded.uuid, meta.uuid);
+        assert!(diff(&meta, &loaded).is_empty());
+
+        let newer =
+            fs::read_to_string(&path)
+                .unwrap()
+                .replacen("\"version\": 2", "\"version\": 99", 1);
+        fs::write(&path, newer).unwrap();
+        assert!(matches!(
+            read_sidecar(&image),
+            Err(CoreError::UnsupportedSchema(99))
+        ));
+    }
+}
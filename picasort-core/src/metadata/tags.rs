@@ -0,0 +1,98 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+// Tag contexts shared by the structs having the same fields, e.g. the dates
+// and camera of `Basics`. The destination is the field name to declare.
+
+use little_exif::exif_tag::ExifTag;
+
+use crate::metadata::exif::{TagContext, extract_offset, extract_string, extract_utc_datetime};
+
+/// EXIF DateTimeDigitized (0x9004, little_exif CreateDate) into
+/// `creation_date`
+pub const DATE_CREATED: TagContext<'static> = TagContext {
+    destination: "creation_date",
+    main_tag: ExifTag::CreateDate(String::new()),
+    alternative: None,
+    convert: extract_utc_datetime,
+};
+
+/// EXIF DateTimeOriginal into `original_date`
+pub const DATE_ORIGINAL: TagContext<'static> = TagContext {
+    destination: "original_date",
+    main_tag: ExifTag::DateTimeOriginal(String::new()),
+    alternative: None,
+    convert: extract_utc_datetime,
+};
+
+/// EXIF ModifyDate into `modification_date`
+pub const DATE_MODIFIED: TagContext<'static> = TagContext {
+    destination: "modification_date",
+    main_tag: ExifTag::ModifyDate(String::new()),
+    alternative: None,
+    convert: extract_utc_datetime,
+};
+
+/// EXIF OffsetTimeOriginal into `original_offset`
+pub const OFFSET_ORIGINAL: TagContext<'static> = TagContext {
+    destination: "original_offset",
+    main_tag: ExifTag::OffsetTimeOriginal(String::new()),
+    alternative: None,
+    convert: extract_offset,
+};
+
+/// EXIF OffsetTimeDigitized into `digitized_offset`
+pub const OFFSET_DIGITIZED: TagContext<'static> = TagContext {
+    destination: "digitized_offset",
+    main_tag: ExifTag::OffsetTimeDigitized(String::new()),
+    alternative: None,
+    convert: extract_offset,
+};
+
+/// EXIF OffsetTime into `modification_offset`
+pub const OFFSET_MODIFIED: TagContext<'static> = TagContext {
+    destination: "modification_offset",
+    main_tag: ExifTag::OffsetTime(String::new()),
+    alternative: None,
+    convert: extract_offset,
+};
+
+/// EXIF Copyright into `copyright`
+pub const COPYRIGHT: TagContext<'static> = TagContext {
+    destination: "copyright",
+    main_tag: ExifTag::Copyright(String::new()),
+    alternative: None,
+    convert: extract_string,
+};
+
+/// EXIF Artist into `artist`
+pub const ARTIST: TagContext<'static> = TagContext {
+    destination: "artist",
+    main_tag: ExifTag::Artist(String::new()),
+    alternative: None,
+    convert: extract_string,
+};
+
+/// EXIF Software into `software`
+pub const SOFTWARE: TagContext<'static> = TagContext {
+    destination: "software",
+    main_tag: ExifTag::Software(String::new()),
+    alternative: None,
+    convert: extract_string,
+};
+
+/// EXIF Make into `make`
+pub const CAMERA_MAKE: TagContext<'static> = TagContext {
+    destination: "make",
+    main_tag: ExifTag::Make(String::new()),
+    alternative: None,
+    convert: extract_string,
+};
+
+/// EXIF Model into `model`
+pub const CAMERA_MODEL: TagContext<'static> = TagContext {
+    destination: "model",
+    main_tag: ExifTag::Model(String::new()),
+    alternative: None,
+    convert: extract_string,
+};
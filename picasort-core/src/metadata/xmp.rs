@@ -0,0 +1,99 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+// XMP packet embedded in an image file and its properties, shared by the
+// annotations, the panorama and the motion photo detection.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::error::CoreError;
+
+/// Bytes searched for the embedded XMP packet, which is written at the
+/// start of the file
+pub const XMP_SEARCH_SIZE: usize = 256 * 1024;
+
+/// XMP packet embedded in the file data, None when there is none
+pub fn xmp_packet(data: &[u8]) -> Option<&str> {
+    const START: &[u8] = b"<x:xmpmeta";
+    const END: &[u8] = b"</x:xmpmeta>";
+    let start = data.windows(START.len()).position(|w| w == START)?;
+    let length = data[start..].windows(END.len()).position(|w| w == END)? + END.len();
+    std::str::from_utf8(&data[start..start + length]).ok()
+}
+
+/// XMP packet in the first `XMP_SEARCH_SIZE` bytes of the file data
+pub fn header_xmp_packet(data: &[u8]) -> Option<&str> {
+    xmp_packet(&data[..data.len().min(XMP_SEARCH_SIZE)])
+}
+
+/// XMP packet in the first `XMP_SEARCH_SIZE` bytes of the file at `path`
+pub fn read_xmp_packet<P: AsRef<Path>>(path: P) -> Result<Option<String>, CoreError> {
+    Ok(xmp_packet(&read_header(path)?).map(str::to_string))
+}
+
+/// First `XMP_SEARCH_SIZE` bytes of the file at `path`
+pub(crate) fn read_header<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, CoreError> {
+    let mut header = Vec::new();
+    File::open(path)?
+        .take(XMP_SEARCH_SIZE as u64)
+        .read_to_end(&mut header)?;
+    Ok(header)
+}
+
+/// Value of an XMP property, `name="value"` or `<name>value</name>`
+pub fn xmp_property(xmp: &str, name: &str) -> Option<String> {
+    let attribute = format!("{name}=\"");
+    let element = format!("<{name}>");
+    let value = if let Some(start) = xmp.find(&attribute).map(|i| i + attribute.len()) {
+        &xmp[start..start + xmp[start..].find('"')?]
+    } else {
+        let start = xmp.find(&element)? + element.len();
+        &xmp[start..start + xmp[start..].find(&format!("</{name}>"))?]
+    };
+    Some(
+        value
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_xmp_packet() {
+        assert_eq!(
+            xmp_packet(b"\xFF\xD8<x:xmpmeta a/></x:xmpmeta>\xFF\xD9"),
+            Some("<x:xmpmeta a/></x:xmpmeta>")
+        );
+        assert_eq!(xmp_packet(b"\xFF\xD8<x:xmpmeta a/>"), None);
+
+        let mut data = vec![0; XMP_SEARCH_SIZE];
+        data.extend_from_slice(b"<x:xmpmeta a/></x:xmpmeta>");
+        assert_eq!(header_xmp_packet(&data), None);
+        assert_eq!(
+            header_xmp_packet(&data[XMP_SEARCH_SIZE..]),
+            xmp_packet(&data)
+        );
+
+        let image =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        assert_eq!(read_xmp_packet(&image).unwrap(), None);
+        assert!(read_xmp_packet(image.with_file_name("missing.jpg")).is_err());
+    }
+
+    #[test]
+    fn has_xmp_property() {
+        let xmp = r#"<rdf:Description xmp:Rating="5"><xmp:Label>To &amp; Fro</xmp:Label>"#;
+        assert_eq!(xmp_property(xmp, "xmp:Rating").as_deref(), Some("5"));
+        assert_eq!(xmp_property(xmp, "xmp:Label").as_deref(), Some("To & Fro"));
+        assert_eq!(xmp_property(xmp, "xmp:Rate"), None);
+        assert_eq!(xmp_property(r#"xmp:Rating="5"#, "xmp:Rating"), None);
+    }
+}
@@ -0,0 +1,393 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::error::CoreError;
+use crate::metadata::Metadata;
+use crate::metadata::format::jpeg_segments;
+use crate::metadata::xmp::XMP_SEARCH_SIZE;
+use crate::utils::sha::digest_reader;
+
+/// Bytes read ahead from the start of each file by `scan_directory`. The
+/// EXIF of a JPEG is in its first APP1 segment, within this window for most
+/// cameras, and the XMP is searched in the same bytes as by
+/// `Metadata::from_path`.
+pub const READ_AHEAD_SIZE: usize = XMP_SEARCH_SIZE;
+
+/// Extensions of the image files whose metadata can be read
+pub const IMAGE_EXTENSIONS: [&str; 9] = [
+    "jpg", "jpeg", "png", "tif", "tiff", "heic", "heif", "webp", "jxl",
+];
+
+/// Tells if the path has a known image extension (case insensitive)
+pub fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// Recursively lists the image files under `root`, sorted by path.
+///
+/// Symbolic links are skipped unless `follow_symlinks` is set. When they are
+/// followed, every directory is visited once by its canonical path, so a link
+/// pointing to one of its parents cannot make the scan loop forever.
+pub fn list_image_files(root: &Path, follow_symlinks: bool) -> Result<Vec<PathBuf>, CoreError> {
+    list_files(root, follow_symlinks, is_image_file)
+}
+
+/// Recursively lists the files under `root` accepted by `keep`, sorted by
+/// path. See [`list_image_files`] for `follow_symlinks`.
+pub(crate) fn list_files(
+    root: &Path,
+    follow_symlinks: bool,
+    keep: impl Fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>, CoreError> {
+    let mut files = Vec::new();
+    let mut visited = HashSet::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        if follow_symlinks && !visited.insert(fs::canonicalize(&dir)?) {
+            continue;
+        }
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let mut file_type = entry.file_type()?;
+            if file_type.is_symlink() && follow_symlinks {
+                // Dangling links are ignored
+                let Ok(target) = fs::metadata(entry.path()) else {
+                    continue;
+                };
+                file_type = target.file_type();
+            }
+            if file_type.is_dir() {
+                dirs.push(entry.path());
+            } else if file_type.is_file() && keep(&entry.path()) {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Extracts the metadata of every image under `root`. A file that cannot be
+/// read does not stop the scan, its error is returned in place of its metadata.
+/// See [`list_image_files`] for `follow_symlinks`.
+pub fn scan_directory(
+    root: &Path,
+    follow_symlinks: bool,
+) -> Result<Vec<Result<Metadata, CoreError>>, CoreError> {
+    Ok(list_image_files(root, follow_symlinks)?
+        .iter()
+        .map(|path| read_ahead(path))
+        .collect())
+}
+
+/// Same as `Metadata::from_path`, opening the file once: the EXIF of a JPEG
+/// is parsed from the first `READ_AHEAD_SIZE` bytes and the rest is streamed
+/// to the hash. Other formats, or a JPEG whose EXIF ends beyond the window,
+/// fall back to `Metadata::from_path`.
+fn read_ahead(path: &Path) -> Result<Metadata, CoreError> {
+    let mut file = File::open(path)?;
+    let mut header = Vec::with_capacity(READ_AHEAD_SIZE);
+    (&mut file)
+        .take(READ_AHEAD_SIZE as u64)
+        .read_to_end(&mut header)?;
+    let Some(exif_end) = jpeg_exif_end(&header) else {
+        return Metadata::from_path(path);
+    };
+    let exif = little_exif::metadata::Metadata::new_from_vec(
+        &header[..exif_end].to_vec(),
+        little_exif::filetype::FileExtension::JPEG,
+    )?;
+    let (uuid, _) = digest_reader(&header, file)?;
+    let mut meta = Metadata::from_exif(path.to_path_buf(), uuid, &exif)?;
+    meta.assign_header(&header);
+    Ok(meta)
+}
+
+/// End offset of the EXIF APP1 segment of a JPEG, None when the data is not
+/// a JPEG or does not hold the whole segment
+fn jpeg_exif_end(header: &[u8]) -> Option<usize> {
+    jpeg_segments(header)
+        .find(|s| s.marker == 0xE1 && s.body.starts_with(b"Exif\0\0"))
+        .map(|s| s.end)
+}
+
+/// Extracts the metadata of `paths` in parallel on the rayon global pool,
+/// which has a thread per core. The results are in the order of `paths`.
+pub fn scan_parallel(paths: &[PathBuf]) -> Vec<Result<Metadata, CoreError>> {
+    paths.par_iter().map(|path| read_ahead(path)).collect()
+}
+
+/// Same as [`scan_parallel`] on a dedicated pool of `num_threads` threads, or
+/// on the global pool when `num_threads` is 0.
+///
+/// A thread per core suits SSDs and cached files. On a spinning disk the
+/// threads fight for the head and seeking dominates: 2 to 4 threads are then
+/// faster than 16. Network shares usually sit in between.
+pub fn scan_parallel_with(
+    paths: &[PathBuf],
+    num_threads: usize,
+) -> Result<Vec<Result<Metadata, CoreError>>, CoreError> {
+    if num_threads == 0 {
+        return Ok(scan_parallel(paths));
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()?;
+    Ok(pool.install(|| scan_parallel(paths)))
+}
+
+/// Files read at the same time by [`scan_directory_async`]
+#[cfg(feature = "tokio")]
+pub const ASYNC_SCAN_CONCURRENCY: usize = 16;
+
+/// Asynchronous [`scan_directory`] for tokio applications, reading
+/// [`ASYNC_SCAN_CONCURRENCY`] files at a time. See
+/// [`scan_directory_async_with`].
+#[cfg(feature = "tokio")]
+pub fn scan_directory_async(
+    root: &Path,
+) -> impl futures_util::Stream<Item = Result<Metadata, CoreError>> + use<> {
+    scan_directory_async_with(root, ASYNC_SCAN_CONCURRENCY)
+}
+
+/// Extracts the metadata of every image under `root`, reading up to
+/// `concurrency` files at a time (at least one) with `tokio::fs`. Symbolic
+/// links are skipped. The results come in completion order, not in path
+/// order. An unreadable directory ends the stream with its error, a file that
+/// cannot be read gives its error in place of its metadata.
+///
+/// The EXIF parsing runs on the blocking pool of the runtime, which has to
+/// be a tokio one.
+#[cfg(feature = "tokio")]
+pub fn scan_directory_async_with(
+    root: &Path,
+    concurrency: usize,
+) -> impl futures_util::Stream<Item = Result<Metadata, CoreError>> + use<> {
+    use futures_util::{StreamExt, stream};
+
+    stream::once(list_image_files_async(root.to_path_buf()))
+        .flat_map(|listed| match listed {
+            Ok(paths) => stream::iter(paths.into_iter().map(Ok)).left_stream(),
+            Err(e) => stream::iter([Err(e)]).right_stream(),
+        })
+        .map(|path| async move { read_ahead_async(path?).await })
+        .buffer_unordered(concurrency.max(1))
+}
+
+/// Asynchronous [`list_image_files`], symbolic links being skipped
+#[cfg(feature = "tokio")]
+async fn list_image_files_async(root: PathBuf) -> Result<Vec<PathBuf>, CoreError> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                dirs.push(entry.path());
+            } else if file_type.is_file() && is_image_file(&entry.path()) {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Asynchronous [`read_ahead`]. A file other than a JPEG with its EXIF in
+/// the first `READ_AHEAD_SIZE` bytes falls back to `Metadata::from_path` on
+/// the blocking pool, as the synchronous scan.
+#[cfg(feature = "tokio")]
+async fn read_ahead_async(path: PathBuf) -> Result<Metadata, CoreError> {
+    use tokio::io::AsyncReadExt;
+
+    let mut file = tokio::fs::File::open(&path).await?;
+    let mut header = Vec::with_capacity(READ_AHEAD_SIZE);
+    (&mut file)
+        .take(READ_AHEAD_SIZE as u64)
+        .read_to_end(&mut header)
+        .await?;
+    let Some(exif_end) = jpeg_exif_end(&header) else {
+        return blocking(move || Metadata::from_path(&path)).await;
+    };
+    let (uuid, _) = crate::utils::sha::digest_reader_async(&header, file).await?;
+    blocking(move || {
+        let exif = little_exif::metadata::Metadata::new_from_vec(
+            &header[..exif_end].to_vec(),
+            little_exif::filetype::FileExtension::JPEG,
+        )?;
+        let mut meta = Metadata::from_exif(path, uuid, &exif)?;
+        meta.assign_header(&header);
+        Ok(meta)
+    })
+    .await
+}
+
+/// Runs `f` on the blocking pool, a panic is resumed in the caller
+#[cfg(feature = "tokio")]
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, CoreError> + Send + 'static,
+) -> Result<T, CoreError> {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("a.jpg", true)]
+    #[case("a.JPEG", true)]
+    #[case("dir/a.png", true)]
+    #[case("a.txt", false)]
+    #[case("jpg", false)]
+    fn has_image_file(#[case] path: &str, #[case] expected: bool) {
+        assert_eq!(is_image_file(Path::new(path)), expected);
+    }
+
+    #[test]
+    fn has_scanned_directory() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let files = list_image_files(&root, false).unwrap();
+        assert_eq!(files.len(), 2);
+        let scanned = scan_directory(&root, false).unwrap();
+        assert!(scanned.iter().all(|m| m.is_ok()));
+        for (path, meta) in files.iter().zip(scanned) {
+            assert_eq!(meta.unwrap(), Metadata::from_path(path).unwrap());
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn has_same_sync_and_async_scan() {
+        use futures_util::StreamExt;
+
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img");
+        let sync: Vec<Metadata> = scan_directory(&root, false)
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        let mut scanned: Vec<Metadata> = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(scan_directory_async(&root).collect::<Vec<_>>())
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        scanned.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        assert_eq!(scanned, sync);
+    }
+
+    #[test]
+    fn has_jpeg_exif_end() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        let data = fs::read(path).unwrap();
+        let end = jpeg_exif_end(&data).unwrap();
+        assert_eq!(data[end], 0xFF);
+        assert_eq!(jpeg_exif_end(&data[..end]), Some(end));
+        assert_eq!(jpeg_exif_end(&data[..end - 1]), None);
+        assert_eq!(jpeg_exif_end(b"\x89PNG"), None);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(3)]
+    fn has_parallel_scan(#[case] num_threads: usize) {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let mut paths = list_image_files(&root, false).unwrap();
+        paths.push(root.join("missing.jpg"));
+        let scanned = scan_parallel_with(&paths, num_threads).unwrap();
+        assert_eq!(scanned.len(), 3);
+        for (path, meta) in paths.iter().zip(&scanned[..2]) {
+            assert_eq!(&meta.as_ref().unwrap().file_path, path);
+        }
+        assert!(scanned[2].is_err());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn has_async_scan() {
+        use futures_util::StreamExt;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        for concurrency in [0, 1, ASYNC_SCAN_CONCURRENCY] {
+            let mut scanned: Vec<Metadata> = runtime
+                .block_on(scan_directory_async_with(&root, concurrency).collect::<Vec<_>>())
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+            scanned.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+            let files = list_image_files(&root, false).unwrap();
+            assert_eq!(scanned.len(), files.len());
+            for (path, meta) in files.iter().zip(scanned) {
+                assert_eq!(meta, Metadata::from_path(path).unwrap());
+            }
+        }
+        let missing: Vec<_> =
+            runtime.block_on(scan_directory_async(&root.join("missing")).collect::<Vec<_>>());
+        assert_eq!(missing.len(), 1);
+        assert!(matches!(missing[0], Err(CoreError::IO(_))));
+        let digest = runtime
+            .block_on(crate::utils::sha::get_file_digest_async(
+                root.join("img/text_icon_gps.jpg"),
+            ))
+            .unwrap();
+        assert_eq!(
+            digest,
+            crate::utils::sha::get_file_digest(root.join("img/text_icon_gps.jpg")).unwrap()
+        );
+        let digest = runtime
+            .block_on(crate::utils::sha::get_file_digest_async_with::<
+                sha2::Sha512,
+                _,
+            >(root.join("img/text_icon_gps.jpg")))
+            .unwrap();
+        assert_eq!(
+            digest,
+            crate::utils::sha::get_file_digest_with::<sha2::Sha512, _>(
+                root.join("img/text_icon_gps.jpg")
+            )
+            .unwrap()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn has_followed_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let root = TempDir::new();
+        let photos = root.join("photos");
+        let album = root.join("album");
+        fs::create_dir_all(&photos).unwrap();
+        fs::create_dir_all(&album).unwrap();
+        fs::write(photos.join("a.jpg"), b"").unwrap();
+        symlink(&photos, album.join("photos")).unwrap();
+        // A link to its own parent would loop without cycle protection
+        symlink(&album, album.join("loop")).unwrap();
+        symlink(root.join("missing"), album.join("dangling.jpg")).unwrap();
+
+        assert!(list_image_files(&album, false).unwrap().is_empty());
+        let files = list_image_files(&album, true).unwrap();
+        assert_eq!(files, vec![album.join("photos/a.jpg")]);
+    }
+}
@@ -0,0 +1,985 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::error::CoreError;
+use crate::metadata::Metadata;
+use crate::metadata::export::{json_or_null, json_string};
+use crate::utils::path::sanitize_path_component;
+use crate::utils::sha::{cas_path, get_file_uuid, short_uuid};
+
+/// Date format of the `{date}` name token when none is given
+pub const DEFAULT_NAME_DATE_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// Reason of the moves planned by `plan_moves`
+const MOVE_REASON: &str = "outside its date folder";
+
+/// A single planned move of an image to its destination
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovePlan {
+    pub src: PathBuf,
+    pub dst: PathBuf,
+    /// Best EXIF date of the image, if any
+    pub date: Option<DateTime<Utc>>,
+}
+
+/// What to do with the file modification time when moving it
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MtimePolicy {
+    /// Keep the original modification time
+    #[default]
+    Preserve,
+    /// Set the modification time to the EXIF date, left untouched for undated images
+    FromExif,
+}
+
+/// Outcome of `apply_moves`
+#[derive(Debug, Default)]
+pub struct AppliedReport {
+    /// Moves that were done (and kept, unless `rolled_back` is set)
+    pub applied: Vec<MovePlan>,
+    /// The move that failed and why, stopping the whole plan
+    pub failed: Option<(MovePlan, CoreError)>,
+    /// The applied moves were reverted after the failure
+    pub rolled_back: bool,
+    /// The applied move that could not be reverted and why, stopping the
+    /// rollback: it and the moves applied before it are left done
+    pub rollback_failed: Option<(MovePlan, CoreError)>,
+}
+
+/// Computes the destination folder of an image from its best date and a
+/// chrono format `pattern` (e.g. `%Y/%m`). Returns `None` for undated images,
+/// when the pattern is not a valid chrono format or when a formatted value
+/// would itself contain a path separator.
+pub fn date_folder_path(meta: &Metadata, root: &Path, pattern: &str) -> Option<PathBuf> {
+    let date = meta.basics.best_date()?;
+    let folder = render_folder(pattern, |segment, out| {
+        write!(out, "{}", date.format(segment))
+    })?;
+    Some(root.join(folder))
+}
+
+/// Same as `date_folder_path` with month and day names in the given locale,
+/// e.g. `%Y/%B` gives `2024/juillet` with `Locale::fr_FR`
+#[cfg(feature = "locales")]
+pub fn date_folder_path_localized(
+    meta: &Metadata,
+    root: &Path,
+    pattern: &str,
+    locale: chrono::Locale,
+) -> Option<PathBuf> {
+    let date = meta.basics.best_date()?;
+    let folder = render_folder(pattern, |segment, out| {
+        write!(out, "{}", date.format_localized(segment, locale))
+    })?;
+    Some(root.join(folder))
+}
+
+/// Renders each `/` separated segment of the pattern on its own so that the
+/// only separators of the folder are the ones of the pattern
+fn render_folder<F>(pattern: &str, render: F) -> Option<PathBuf>
+where
+    F: Fn(&str, &mut String) -> std::fmt::Result,
+{
+    let mut folder = PathBuf::new();
+    for segment in pattern.split('/').filter(|s| !s.is_empty()) {
+        let mut rendered = String::new();
+        render(segment, &mut rendered).ok()?;
+        if rendered.contains(['/', '\\']) {
+            return None;
+        }
+        folder.push(rendered);
+    }
+    Some(folder)
+}
+
+/// Tells if the image is not already in its target date folder
+pub fn needs_move(meta: &Metadata, root: &Path, pattern: &str) -> bool {
+    let Some(target) = date_folder_path(meta, root, pattern) else {
+        return false;
+    };
+    match meta.file_path.parent() {
+        Some(current) => !same_dir(current, &target),
+        None => true,
+    }
+}
+
+/// Dry-run planner: lists the moves needed to sort `items` under `root`
+pub fn plan_moves(items: &[Metadata], root: &Path, pattern: &str) -> Vec<MovePlan> {
+    items
+        .iter()
+        .filter_map(|meta| plan_move(meta, root, pattern))
+        .collect()
+}
+
+/// Same as `plan_moves`, writing each move as soon as it is planned, as a
+/// JSON line `{"src":…,"dst":…,"date":…,"reason":…}`, to keep the memory
+/// flat on large libraries. Returns the number of moves written.
+pub fn stream_plan<I, W>(
+    items: I,
+    root: &Path,
+    pattern: &str,
+    mut out: W,
+) -> Result<usize, CoreError>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Metadata>,
+    W: io::Write,
+{
+    let mut count = 0;
+    for meta in items {
+        let Some(mv) = plan_move(meta.borrow(), root, pattern) else {
+            continue;
+        };
+        writeln!(
+            out,
+            "{{\"src\":{},\"dst\":{},\"date\":{},\"reason\":{}}}",
+            json_string(&mv.src.to_string_lossy()),
+            json_string(&mv.dst.to_string_lossy()),
+            json_or_null(mv.date.map(|d| d.to_rfc3339())),
+            json_string(MOVE_REASON),
+        )?;
+        count += 1;
+    }
+    out.flush()?;
+    Ok(count)
+}
+
+fn plan_move(meta: &Metadata, root: &Path, pattern: &str) -> Option<MovePlan> {
+    if !needs_move(meta, root, pattern) {
+        return None;
+    }
+    let folder = date_folder_path(meta, root, pattern)?;
+    let file_name = meta.file_path.file_name()?;
+    Some(MovePlan {
+        src: meta.file_path.clone(),
+        dst: folder.join(file_name),
+        date: meta.basics.best_date(),
+    })
+}
+
+/// Executes the moves of `plan` in order. If a move fails, the previously
+/// applied ones are moved back to their source so the library is never left
+/// half-sorted. When the rollback itself fails, the report tells which move
+/// was not reverted in `rollback_failed`.
+/// With `MtimePolicy::FromExif`, the moved files get their EXIF date as
+/// modification time so OS date sorting matches the EXIF one.
+pub fn apply_moves(plan: &[MovePlan], mtime: MtimePolicy) -> AppliedReport {
+    let mut report = AppliedReport::default();
+    let mut previous_mtimes = Vec::new();
+    for mv in plan {
+        if let Err(e) = move_file(&mv.src, &mv.dst) {
+            report.failed = Some((mv.clone(), e));
+            break;
+        }
+        report.applied.push(mv.clone());
+        let date = match (mtime, mv.date) {
+            (MtimePolicy::FromExif, Some(date)) => date,
+            _ => {
+                previous_mtimes.push(None);
+                continue;
+            }
+        };
+        match set_mtime(&mv.dst, date.into()) {
+            Ok(previous) => previous_mtimes.push(Some(previous)),
+            Err(e) => {
+                previous_mtimes.push(None);
+                report.failed = Some((mv.clone(), e));
+                break;
+            }
+        }
+    }
+    if report.failed.is_some() {
+        for (mv, previous) in report.applied.iter().zip(previous_mtimes).rev() {
+            let reverted = move_file(&mv.dst, &mv.src).and_then(|()| match previous {
+                Some(previous) => set_mtime(&mv.src, previous).map(|_| ()),
+                None => Ok(()),
+            });
+            if let Err(e) = reverted {
+                report.rollback_failed = Some((mv.clone(), e));
+                break;
+            }
+        }
+        report.rolled_back = !report.applied.is_empty() && report.rollback_failed.is_none();
+    }
+    report
+}
+
+/// Sets the modification time of a file and returns the previous one
+fn set_mtime(path: &Path, time: SystemTime) -> Result<SystemTime, CoreError> {
+    let file = File::options().write(true).open(path)?;
+    let previous = file.metadata()?.modified()?;
+    file.set_modified(time)?;
+    Ok(previous)
+}
+
+/// Moves a file, never overwriting the destination. Falls back to
+/// `move_across_devices` when source and destination are on different
+/// filesystems (EXDEV), e.g. a network drive.
+fn move_file(src: &Path, dst: &Path) -> Result<(), CoreError> {
+    if dst.exists() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("destination {} already exists", dst.display()),
+        )
+        .into());
+    }
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match fs::rename(src, dst) {
+        Err(e) if e.kind() == ErrorKind::CrossesDevices => move_across_devices(src, dst),
+        res => Ok(res?),
+    }
+}
+
+/// Streams the content to the destination, syncs it to disk and keeps the
+/// modification time. The source is only removed once the content hashes of
+/// both files match, a partial destination is removed on failure.
+fn move_across_devices(src: &Path, dst: &Path) -> Result<(), CoreError> {
+    let mut reader = File::open(src)?;
+    let modified = reader.metadata()?.modified()?;
+    let mut writer = File::create_new(dst)?;
+    let copied = (|| {
+        io::copy(&mut reader, &mut writer)?;
+        writer.set_modified(modified)?;
+        writer.sync_all()?;
+        if get_file_uuid(src)? != get_file_uuid(dst)? {
+            return Err(CoreError::ContentMismatch(dst.display().to_string()));
+        }
+        Ok(())
+    })();
+    if let Err(e) = copied {
+        drop(writer);
+        fs::remove_file(dst)?;
+        return Err(e);
+    }
+    fs::remove_file(src)?;
+    Ok(())
+}
+
+/// Renders a file name from a template like `{date:%Y%m%d}_{camera}_{seq}.jpg`.
+/// The tokens are:
+/// - `{date}` or `{date:<chrono format>}`: best date, `DEFAULT_NAME_DATE_FORMAT` by default
+/// - `{camera}`: make and model, the make being omitted when the model starts with it
+/// - `{seq}` or `{seq:<width>}`: `seq`, zero padded to `width`, telling apart
+///   photos taken in the same second
+/// - `{orig}`: original file name without extension
+/// - `{uuid8}`: first 8 characters of the uuid
+///
+/// Each substituted value is sanitized as a path component. Unknown tokens,
+/// unclosed braces and path separators in the template are `InvalidTemplate`
+/// errors, tokens without a value in the metadata `MissingTemplateValue` ones.
+pub fn render_name(meta: &Metadata, template: &str, seq: usize) -> Result<String, CoreError> {
+    render_name_padded(meta, template, seq, 0)
+}
+
+/// `render_name` with `{seq}` zero padded to at least `seq_width`
+fn render_name_padded(
+    meta: &Metadata,
+    template: &str,
+    seq: usize,
+    seq_width: usize,
+) -> Result<String, CoreError> {
+    let mut name = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        push_literal(&mut name, &rest[..start])?;
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| CoreError::InvalidTemplate(format!("unclosed token in {template}")))?;
+        let token = &rest[start + 1..start + end];
+        let value = render_token(meta, token, seq, seq_width)?;
+        name.push_str(&sanitize_path_component(&value));
+        rest = &rest[start + end + 1..];
+    }
+    push_literal(&mut name, rest)?;
+    Ok(name)
+}
+
+fn push_literal(name: &mut String, literal: &str) -> Result<(), CoreError> {
+    if literal.contains(['/', '\\', '}']) {
+        return Err(CoreError::InvalidTemplate(format!(
+            "unexpected character in {literal}"
+        )));
+    }
+    name.push_str(literal);
+    Ok(())
+}
+
+fn render_token(
+    meta: &Metadata,
+    token: &str,
+    seq: usize,
+    seq_width: usize,
+) -> Result<String, CoreError> {
+    let missing = || CoreError::MissingTemplateValue(token.to_string());
+    let (key, arg) = match token.split_once(':') {
+        Some((key, arg)) => (key, Some(arg)),
+        None => (token, None),
+    };
+    match (key, arg) {
+        ("date", format) => {
+            let date = meta.basics.best_date().ok_or_else(missing)?;
+            let mut rendered = String::new();
+            write!(
+                rendered,
+                "{}",
+                date.format(format.unwrap_or(DEFAULT_NAME_DATE_FORMAT))
+            )
+            .map_err(|_| CoreError::InvalidTemplate(format!("bad date format in {{{token}}}")))?;
+            Ok(rendered)
+        }
+        ("seq", None) => Ok(format!("{seq:0seq_width$}")),
+        ("seq", Some(width)) => {
+            let width: usize = width
+                .parse()
+                .map_err(|_| CoreError::InvalidTemplate(format!("bad width in {{{token}}}")))?;
+            let width = width.max(seq_width);
+            Ok(format!("{seq:0width$}"))
+        }
+        ("camera", None) => meta.basics.camera_name().ok_or_else(missing),
+        ("orig", None) => meta
+            .file_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .ok_or_else(missing),
+        ("uuid8", None) => short_uuid(&meta.uuid, 8)
+            .map(str::to_string)
+            .ok_or_else(missing),
+        _ => Err(CoreError::InvalidTemplate(format!(
+            "unknown token {{{token}}}"
+        ))),
+    }
+}
+
+/// Plans moving and renaming `items` into their date folder under `root`,
+/// the name being rendered from `template` (see `render_name`). In each
+/// destination folder the photos are numbered from 1 by date, the `{seq}`
+/// numbers being zero padded to `pad_width`, or to the digits of the folder
+/// photo count when larger, so that the names sort chronologically.
+/// Undated photos and photos already at their destination are left out.
+pub fn plan_renames(
+    items: &[Metadata],
+    root: &Path,
+    pattern: &str,
+    template: &str,
+    pad_width: usize,
+) -> Result<Vec<MovePlan>, CoreError> {
+    let mut folders: BTreeMap<PathBuf, Vec<(DateTime<Utc>, &Metadata)>> = BTreeMap::new();
+    for meta in items {
+        if let (Some(folder), Some(date)) = (
+            date_folder_path(meta, root, pattern),
+            meta.basics.best_date(),
+        ) {
+            folders.entry(folder).or_default().push((date, meta));
+        }
+    }
+
+    let mut plan = Vec::new();
+    for (folder, mut group) in folders {
+        group.sort_by(|(a, ma), (b, mb)| a.cmp(b).then_with(|| ma.file_path.cmp(&mb.file_path)));
+        let width = pad_width.max(group.len().to_string().len());
+        for (index, (date, meta)) in group.into_iter().enumerate() {
+            let dst = folder.join(render_name_padded(meta, template, index + 1, width)?);
+            if dst != meta.file_path {
+                plan.push(MovePlan {
+                    src: meta.file_path.clone(),
+                    dst,
+                    date: Some(date),
+                });
+            }
+        }
+    }
+    Ok(plan)
+}
+
+/// Renders `template` (see `render_name`) for every photo and maps each
+/// name to the indices of the photos giving it: a name with more than one
+/// index is a collision. `{seq}` is the position of the photo in `items`,
+/// from 1. Photos missing a token value are left out, an invalid template
+/// is an `InvalidTemplate` error.
+pub fn collision_report(
+    items: &[Metadata],
+    template: &str,
+) -> Result<HashMap<String, Vec<usize>>, CoreError> {
+    let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, meta) in items.iter().enumerate() {
+        match render_name(meta, template, index + 1) {
+            Ok(name) => names.entry(name).or_default().push(index),
+            Err(CoreError::MissingTemplateValue(_)) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(names)
+}
+
+/// Plans moving `items` into a content-addressable store under `root`, laid
+/// out by uuid (see `utils::cas_path`) instead of by date. Identical photos
+/// share a destination: only the first one is planned, the others are left
+/// in place as duplicates. Photos without a hex uuid and photos already at
+/// their destination are left out.
+pub fn plan_cas_moves(items: &[Metadata], root: &Path) -> Vec<MovePlan> {
+    let destinations: Vec<_> = items
+        .iter()
+        .map(|meta| {
+            let ext = meta
+                .file_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or_default();
+            cas_path(&meta.uuid, ext).map(|path| root.join(path))
+        })
+        .collect();
+    // Photos already stored take their destination first
+    let mut taken: HashSet<&PathBuf> = items
+        .iter()
+        .zip(&destinations)
+        .filter_map(|(meta, dst)| dst.as_ref().filter(|dst| **dst == meta.file_path))
+        .collect();
+    let mut plan = Vec::new();
+    for (meta, dst) in items.iter().zip(&destinations) {
+        let Some(dst) = dst else {
+            continue;
+        };
+        if taken.insert(dst) {
+            plan.push(MovePlan {
+                src: meta.file_path.clone(),
+                dst: dst.clone(),
+                date: meta.basics.best_date(),
+            });
+        }
+    }
+    plan
+}
+
+/// Groups the indices of the photos by EXIF artist, e.g. to separate the
+/// photographers of a shared library. Photos without artist are left out.
+pub fn group_by_artist(items: &[Metadata]) -> BTreeMap<String, Vec<usize>> {
+    let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, meta) in items.iter().enumerate() {
+        if let Some(artist) = meta.basics.artist.as_deref().map(str::trim)
+            && !artist.is_empty()
+        {
+            groups.entry(artist.to_string()).or_default().push(index);
+        }
+    }
+    groups
+}
+
+/// Groups the indices of photos taken in burst mode: sorted by best date
+/// (with sub-second precision when the camera wrote it), consecutive photos
+/// at most `max_gap` apart end up in the same group. Only groups of at least
+/// two photos are returned, undated photos are ignored.
+pub fn group_bursts(items: &[Metadata], max_gap: Duration) -> Vec<Vec<usize>> {
+    let max_gap = TimeDelta::from_std(max_gap).unwrap_or(TimeDelta::MAX);
+    let mut dated: Vec<(DateTime<Utc>, usize)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, meta)| Some((meta.basics.best_date()?, index)))
+        .collect();
+    dated.sort();
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut previous: Option<DateTime<Utc>> = None;
+    for (date, index) in dated {
+        match (previous, groups.last_mut()) {
+            (Some(p), Some(group)) if date - p <= max_gap => group.push(index),
+            _ => groups.push(vec![index]),
+        }
+        previous = Some(date);
+    }
+    groups.retain(|g| g.len() > 1);
+    groups
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use chrono::DateTime;
+    use rstest::rstest;
+
+    fn get_meta(file_path: &str, date: Option<&str>) -> Metadata {
+        let mut meta = Metadata {
+            file_path: PathBuf::from(file_path),
+            ..Default::default()
+        };
+        meta.basics.original_date = date.map(|d| DateTime::parse_from_rfc3339(d).unwrap().to_utc());
+        meta
+    }
+
+    #[test]
+    fn has_planned_cas_moves() {
+        let with_uuid = |file_path: &str, uuid: &str| Metadata {
+            uuid: uuid.to_string(),
+            ..get_meta(file_path, Some("2024-10-28T20:35:03Z"))
+        };
+        let items = vec![
+            with_uuid("/in/a.JPG", "ab12cd"),
+            with_uuid("/in/copy of a.jpg", "ab12cd"),
+            with_uuid("/in/b.png", "ef34"),
+            with_uuid("/store/ef/34.png", "ef34"),
+            with_uuid("/in/c.jpeg", "5678"),
+            with_uuid("/store/01/23.jpg", "0123"),
+            with_uuid("/in/no-uuid.jpg", ""),
+        ];
+        let plan = plan_cas_moves(&items, Path::new("/store"));
+        let moves: Vec<_> = plan
+            .iter()
+            .map(|mv| (mv.src.to_str().unwrap(), mv.dst.to_str().unwrap()))
+            .collect();
+        assert_eq!(
+            moves,
+            vec![
+                ("/in/a.JPG", "/store/ab/12cd.jpg"),
+                ("/in/c.jpeg", "/store/56/78.jpeg"),
+            ]
+        );
+        assert_eq!(plan[0].date, items[0].basics.best_date());
+    }
+
+    #[rstest]
+    #[case(
+        "/photos/2024/10/img.jpg",
+        Some("2024-10-28T20:35:03Z"),
+        "%Y/%m",
+        false
+    )]
+    #[case("/photos/img.jpg", Some("2024-10-28T20:35:03Z"), "%Y/%m", true)]
+    #[case("/photos/2024/11/img.jpg", Some("2024-10-28T20:35:03Z"), "%Y/%m", true)]
+    #[case(
+        "/photos/2024-10-28/img.jpg",
+        Some("2024-10-28T20:35:03Z"),
+        "%Y-%m-%d",
+        false
+    )]
+    #[case("/photos/img.jpg", None, "%Y/%m", false)]
+    #[case("/photos/img.jpg", Some("2024-10-28T20:35:03Z"), "%Q", false)]
+    fn has_needs_move(
+        #[case] file_path: &str,
+        #[case] date: Option<&str>,
+        #[case] pattern: &str,
+        #[case] expected: bool,
+    ) {
+        let meta = get_meta(file_path, date);
+        assert_eq!(needs_move(&meta, Path::new("/photos"), pattern), expected);
+    }
+
+    #[cfg(feature = "locales")]
+    #[rstest]
+    #[case(chrono::Locale::fr_FR, "%Y/%B", "/photos/2024/juillet")]
+    #[case(chrono::Locale::en_US, "%Y/%B", "/photos/2024/July")]
+    #[case(chrono::Locale::de_DE, "%Y/%m %B/%A", "/photos/2024/07 Juli/Freitag")]
+    fn has_localized_date_folder(
+        #[case] locale: chrono::Locale,
+        #[case] pattern: &str,
+        #[case] expected: &str,
+    ) {
+        let meta = get_meta("/photos/img.jpg", Some("2024-07-12T10:00:00Z"));
+        assert_eq!(
+            date_folder_path_localized(&meta, Path::new("/photos"), pattern, locale),
+            Some(PathBuf::from(expected))
+        );
+    }
+
+    #[rstest]
+    #[case("%Y/%m", Some("/photos/2024/07"))]
+    #[case("%Y//%m/", Some("/photos/2024/07"))]
+    #[case("%D", None)]
+    fn has_date_folder(#[case] pattern: &str, #[case] expected: Option<&str>) {
+        let meta = get_meta("/photos/img.jpg", Some("2024-07-12T10:00:00Z"));
+        assert_eq!(
+            date_folder_path(&meta, Path::new("/photos"), pattern),
+            expected.map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn has_bursts() {
+        let items = vec![
+            get_meta("/photos/a.jpg", Some("2024-10-28T20:35:03.100Z")),
+            get_meta("/photos/b.jpg", Some("2024-10-28T20:40:00Z")),
+            get_meta("/photos/c.jpg", Some("2024-10-28T20:35:03.350Z")),
+            get_meta("/photos/d.jpg", None),
+            get_meta("/photos/e.jpg", Some("2024-10-28T20:35:03.500Z")),
+            get_meta("/photos/f.jpg", Some("2024-10-28T20:35:04.500Z")),
+        ];
+        assert_eq!(
+            group_bursts(&items, Duration::from_millis(250)),
+            vec![vec![0, 2, 4]]
+        );
+        assert_eq!(
+            group_bursts(&items, Duration::from_secs(1)),
+            vec![vec![0, 2, 4, 5]]
+        );
+        assert!(group_bursts(&items, Duration::from_millis(100)).is_empty());
+    }
+
+    #[test]
+    fn has_plan_moves() {
+        let items = vec![
+            get_meta("/photos/2024/10/a.jpg", Some("2024-10-28T20:35:03Z")),
+            get_meta("/photos/b.jpg", Some("2024-10-28T20:35:03Z")),
+            get_meta("/photos/c.jpg", None),
+        ];
+        let plan = plan_moves(&items, Path::new("/photos"), "%Y/%m");
+        assert_eq!(
+            plan,
+            vec![MovePlan {
+                src: PathBuf::from("/photos/b.jpg"),
+                dst: PathBuf::from("/photos/2024/10/b.jpg"),
+                date: Some(
+                    DateTime::parse_from_rfc3339("2024-10-28T20:35:03Z")
+                        .unwrap()
+                        .to_utc()
+                ),
+            }]
+        );
+    }
+
+    #[test]
+    fn has_streamed_plan() {
+        let items = vec![
+            get_meta("/photos/2024/10/a.jpg", Some("2024-10-28T20:35:03Z")),
+            get_meta("/photos/b \"1\".jpg", Some("2024-10-28T20:35:03Z")),
+            get_meta("/photos/c.jpg", None),
+            get_meta("/photos/d.jpg", Some("2023-01-02T03:04:05Z")),
+        ];
+        let mut out = Vec::new();
+        let count = stream_plan(&items, Path::new("/photos"), "%Y/%m", &mut out).unwrap();
+        assert_eq!(count, 2);
+        let lines: Vec<&str> = std::str::from_utf8(&out).unwrap().lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"src":"/photos/b \"1\".jpg","dst":"/photos/2024/10/b \"1\".jpg","date":"2024-10-28T20:35:03+00:00","reason":"outside its date folder"}"#,
+                r#"{"src":"/photos/d.jpg","dst":"/photos/2023/01/d.jpg","date":"2023-01-02T03:04:05+00:00","reason":"outside its date folder"}"#,
+            ]
+        );
+        assert_eq!(
+            plan_moves(&items, Path::new("/photos"), "%Y/%m").len(),
+            count
+        );
+
+        let mut out = Vec::new();
+        assert_eq!(
+            stream_plan(
+                items.into_iter().take(1),
+                Path::new("/photos"),
+                "%Y/%m",
+                &mut out
+            )
+            .unwrap(),
+            0
+        );
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn has_applied_moves() {
+        let root = TempDir::new();
+        fs::write(root.join("a.jpg"), b"a").unwrap();
+        let plan = vec![MovePlan {
+            src: root.join("a.jpg"),
+            dst: root.join("2024/a.jpg"),
+            date: None,
+        }];
+        let report = apply_moves(&plan, MtimePolicy::Preserve);
+        assert_eq!(report.applied, plan);
+        assert!(report.failed.is_none());
+        assert!(root.join("2024/a.jpg").exists());
+        assert!(!root.join("a.jpg").exists());
+    }
+
+    #[test]
+    fn has_rollback_on_failure() {
+        let root = TempDir::new();
+        fs::write(root.join("a.jpg"), b"a").unwrap();
+        let plan = vec![
+            MovePlan {
+                src: root.join("a.jpg"),
+                dst: root.join("2024/a.jpg"),
+                date: None,
+            },
+            MovePlan {
+                src: root.join("missing.jpg"),
+                dst: root.join("2024/missing.jpg"),
+                date: None,
+            },
+        ];
+        let report = apply_moves(&plan, MtimePolicy::Preserve);
+        assert!(report.rolled_back);
+        assert!(matches!(report.failed, Some((_, CoreError::IO(_)))));
+        assert!(report.rollback_failed.is_none());
+        assert!(root.join("a.jpg").exists());
+        assert!(!root.join("2024/a.jpg").exists());
+    }
+
+    #[test]
+    fn has_report_of_failed_rollback() {
+        let root = TempDir::new();
+        fs::write(root.join("a.jpg"), b"a").unwrap();
+        fs::write(root.join("b.jpg"), b"b").unwrap();
+        let mv = |src: &str, dst: &str| MovePlan {
+            src: root.join(src),
+            dst: root.join(dst),
+            date: None,
+        };
+        // Reverting the second move leaves the folder "a.jpg", in the way of
+        // reverting the first one
+        let plan = vec![
+            mv("a.jpg", "2024/a.jpg"),
+            mv("b.jpg", "a.jpg/b.jpg"),
+            mv("missing.jpg", "2024/missing.jpg"),
+        ];
+        let report = apply_moves(&plan, MtimePolicy::Preserve);
+        assert_eq!(report.applied.len(), 2);
+        assert!(matches!(report.failed, Some((_, CoreError::IO(_)))));
+        assert!(!report.rolled_back);
+        assert!(matches!(
+            &report.rollback_failed,
+            Some((failed, CoreError::IO(_))) if *failed == plan[0]
+        ));
+        assert!(root.join("b.jpg").exists());
+        assert!(root.join("2024/a.jpg").exists());
+    }
+
+    #[test]
+    fn has_move_across_devices() {
+        let root = TempDir::new();
+        let src = root.join("a.jpg");
+        let dst = root.join("b.jpg");
+        fs::write(&src, b"content").unwrap();
+        let modified =
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        File::options()
+            .write(true)
+            .open(&src)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+
+        move_across_devices(&src, &dst).unwrap();
+        assert!(!src.exists());
+        assert_eq!(fs::read(&dst).unwrap(), b"content");
+        assert_eq!(fs::metadata(&dst).unwrap().modified().unwrap(), modified);
+
+        // Reading a directory fails after the destination was created
+        let folder = root.join("folder");
+        fs::create_dir(&folder).unwrap();
+        assert!(matches!(
+            move_across_devices(&folder, &root.join("c.jpg")),
+            Err(CoreError::IO(_))
+        ));
+        assert!(!root.join("c.jpg").exists());
+        assert!(folder.is_dir());
+    }
+
+    #[rstest]
+    #[case(
+        MtimePolicy::FromExif,
+        Some("2024-10-28T20:35:03Z"),
+        Some("2024-10-28T20:35:03Z")
+    )]
+    #[case(MtimePolicy::FromExif, None, None)]
+    #[case(MtimePolicy::Preserve, Some("2024-10-28T20:35:03Z"), None)]
+    fn has_mtime_policy(
+        #[case] policy: MtimePolicy,
+        #[case] date: Option<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        let root = TempDir::new();
+        fs::write(root.join("a.jpg"), b"a").unwrap();
+        let before = fs::metadata(root.join("a.jpg"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        let plan = vec![MovePlan {
+            src: root.join("a.jpg"),
+            dst: root.join("2024/a.jpg"),
+            date: date.map(|d| DateTime::parse_from_rfc3339(d).unwrap().to_utc()),
+        }];
+        apply_moves(&plan, policy);
+        let after = fs::metadata(root.join("2024/a.jpg"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        match expected {
+            Some(e) => assert_eq!(
+                DateTime::<Utc>::from(after),
+                DateTime::parse_from_rfc3339(e).unwrap().to_utc()
+            ),
+            None => assert_eq!(after, before),
+        }
+    }
+
+    fn get_named_meta() -> Metadata {
+        let mut meta = get_meta("/photos/IMG_0042.JPG", Some("2024-10-28T20:35:03Z"));
+        meta.uuid = "75f5e4ce87df5e44".to_string();
+        meta.basics.make = Some("Canon".to_string());
+        meta.basics.model = Some("Canon EOS R5".to_string());
+        meta
+    }
+
+    #[rstest]
+    #[case("{date:%Y%m%d}_{camera}_{seq}.jpg", "20241028_Canon EOS R5_7.jpg")]
+    #[case("{date}-{seq:3}", "20241028_203503-007")]
+    #[case("{orig}_{uuid8}.jpg", "IMG_0042_75f5e4ce.jpg")]
+    #[case("{date:%Y/%m}", "2024_10")]
+    #[case("plain.jpg", "plain.jpg")]
+    fn has_rendered_name(#[case] template: &str, #[case] expected: &str) {
+        assert_eq!(
+            render_name(&get_named_meta(), template, 7).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("{model}.jpg")]
+    #[case("{date")]
+    #[case("date}")]
+    #[case("{seq:x}")]
+    #[case("{date:%Q}")]
+    #[case("a/{seq}")]
+    fn has_invalid_name_template(#[case] template: &str) {
+        assert!(matches!(
+            render_name(&get_named_meta(), template, 1),
+            Err(CoreError::InvalidTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn has_missing_name_value() {
+        let meta = get_meta("/photos/img.jpg", None);
+        for template in ["{date}", "{camera}", "{uuid8}"] {
+            assert!(matches!(
+                render_name(&meta, template, 1),
+                Err(CoreError::MissingTemplateValue(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn has_collision_report() {
+        let items = vec![
+            get_meta("/in/a.jpg", Some("2024-10-28T20:35:03Z")),
+            get_meta("/in/b.jpg", Some("2024-10-28T20:35:03Z")),
+            get_meta("/in/c.jpg", Some("2024-10-28T20:35:04Z")),
+            get_meta("/in/undated.jpg", None),
+            get_meta("/other/a.jpg", Some("2024-11-02T10:00:00Z")),
+        ];
+        let report = collision_report(&items, "{date}.jpg").unwrap();
+        assert_eq!(report.len(), 3);
+        assert_eq!(report["20241028_203503.jpg"], vec![0, 1]);
+        assert_eq!(report["20241028_203504.jpg"], vec![2]);
+
+        let report = collision_report(&items, "{orig}.jpg").unwrap();
+        assert_eq!(report["a.jpg"], vec![0, 4]);
+        let report = collision_report(&items, "{date}_{seq}").unwrap();
+        assert!(report.values().all(|indices| indices.len() == 1));
+        assert!(matches!(
+            collision_report(&items, "{nope}"),
+            Err(CoreError::InvalidTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn has_camera_name_with_make() {
+        let mut meta = get_named_meta();
+        meta.basics.make = Some("NIKON CORPORATION".to_string());
+        meta.basics.model = Some("NIKON Z 6".to_string());
+        assert_eq!(
+            render_name(&meta, "{camera}", 1).unwrap(),
+            "NIKON CORPORATION NIKON Z 6"
+        );
+    }
+
+    #[rstest]
+    #[case(3, &["001", "002", "003"])]
+    #[case(0, &["1", "2", "3"])]
+    fn has_planned_renames(#[case] pad_width: usize, #[case] expected: &[&str]) {
+        let items = vec![
+            get_meta("/in/c.jpg", Some("2024-10-28T20:35:05Z")),
+            get_meta("/in/a.jpg", Some("2024-10-28T20:35:03Z")),
+            get_meta("/in/b.jpg", Some("2024-10-28T20:35:03Z")),
+            get_meta("/in/other.jpg", Some("2024-11-02T10:00:00Z")),
+            get_meta("/in/undated.jpg", None),
+        ];
+        let plan = plan_renames(
+            &items,
+            Path::new("/out"),
+            "%Y/%m",
+            "{seq}_{orig}",
+            pad_width,
+        )
+        .unwrap();
+        let names: Vec<(&Path, String)> = plan
+            .iter()
+            .map(|mv| (mv.src.as_path(), mv.dst.display().to_string()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (
+                    Path::new("/in/a.jpg"),
+                    format!("/out/2024/10/{}_a", expected[0])
+                ),
+                (
+                    Path::new("/in/b.jpg"),
+                    format!("/out/2024/10/{}_b", expected[1])
+                ),
+                (
+                    Path::new("/in/c.jpg"),
+                    format!("/out/2024/10/{}_c", expected[2])
+                ),
+                (
+                    Path::new("/in/other.jpg"),
+                    format!("/out/2024/11/{}_other", expected[0])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn has_expanded_sequence_width() {
+        let items: Vec<Metadata> = (0..12)
+            .map(|i| get_meta(&format!("/in/{i:02}.jpg"), Some("2024-10-28T20:35:03Z")))
+            .collect();
+        let plan = plan_renames(&items, Path::new("/out"), "%Y", "{seq}", 1).unwrap();
+        assert_eq!(plan[0].dst, PathBuf::from("/out/2024/01"));
+        assert_eq!(plan[11].dst, PathBuf::from("/out/2024/12"));
+        let plan = plan_renames(&items, Path::new("/out"), "%Y", "{seq:1}", 0).unwrap();
+        assert_eq!(plan[0].dst, PathBuf::from("/out/2024/01"));
+        assert!(plan_renames(&items, Path::new("/out"), "%Y", "{nope}", 3).is_err());
+    }
+
+    #[test]
+    fn has_artist_groups() {
+        let mut items: Vec<Metadata> = (0..4).map(|_| Metadata::default()).collect();
+        items[0].basics.artist = Some("Alice".to_string());
+        items[1].basics.artist = Some("Bob ".to_string());
+        items[2].basics.artist = Some("Alice".to_string());
+        items[3].basics.artist = Some(" ".to_string());
+        let groups = group_by_artist(&items);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["Alice"], vec![0, 2]);
+        assert_eq!(groups["Bob"], vec![1]);
+    }
+}
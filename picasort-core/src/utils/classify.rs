@@ -0,0 +1,161 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::error::CoreError;
+use crate::metadata::format::ImageFormat;
+use crate::scan::{is_image_file, list_files};
+use crate::utils::motion::VIDEO_EXTENSIONS;
+
+/// Extensions of the files holding the metadata of another one: XMP, Apple
+/// edits (AAE), Google Takeout JSON and video thumbnails (THM)
+pub const SIDECAR_EXTENSIONS: [&str; 4] = ["xmp", "aae", "json", "thm"];
+
+/// Bytes read from the start of a file to find its type
+const MAGIC_SIZE: u64 = 16;
+
+/// Kind of a file in a photo library
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileClass {
+    Image,
+    Video,
+    /// Metadata of another file, e.g. an XMP
+    Sidecar,
+    Other,
+}
+
+/// Files of a directory by kind, each list sorted by path
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PartitionedFiles {
+    pub images: Vec<PathBuf>,
+    pub videos: Vec<PathBuf>,
+    /// Sidecars and other files
+    pub other: Vec<PathBuf>,
+}
+
+/// Kind of the file at `path`, from its magic bytes and else from its
+/// extension (case insensitive). A file that cannot be read is classified
+/// by its extension.
+pub fn classify_path(path: &Path) -> FileClass {
+    let mut header = Vec::new();
+    if let Ok(file) = File::open(path) {
+        let _ = file.take(MAGIC_SIZE).read_to_end(&mut header);
+    }
+    if ImageFormat::detect(&header).is_some() {
+        return FileClass::Image;
+    }
+    if is_video_header(&header) {
+        return FileClass::Video;
+    }
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if is_image_file(path) {
+        FileClass::Image
+    } else if VIDEO_EXTENSIONS.contains(&extension.as_str()) {
+        FileClass::Video
+    } else if SIDECAR_EXTENSIONS.contains(&extension.as_str()) {
+        FileClass::Sidecar
+    } else {
+        FileClass::Other
+    }
+}
+
+/// ISO base media files (MP4, MOV) not detected as HEIF images, and AVI
+fn is_video_header(header: &[u8]) -> bool {
+    matches!(header.get(4..8), Some(b"ftyp" | b"moov"))
+        || (header.starts_with(b"RIFF") && header.get(8..12) == Some(b"AVI "))
+}
+
+/// Recursively splits the files under `root` into images, videos and the
+/// others, symbolic links being skipped
+pub fn partition_directory(root: &Path) -> Result<PartitionedFiles, CoreError> {
+    let mut partitioned = PartitionedFiles::default();
+    for path in list_files(root, false, |_| true)? {
+        match classify_path(&path) {
+            FileClass::Image => partitioned.images.push(path),
+            FileClass::Video => partitioned.videos.push(path),
+            FileClass::Sidecar | FileClass::Other => partitioned.other.push(path),
+        }
+    }
+    Ok(partitioned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::fs;
+
+    #[test]
+    fn has_classified_paths() {
+        let root = TempDir::new();
+        fs::create_dir_all(root.join("2024")).unwrap();
+        let files: [(&str, &[u8]); 9] = [
+            ("IMG_0001.HEIC", b"\0\0\0\x18ftypheic\0\0\0\0"),
+            ("IMG_0001.MOV", b"\0\0\0\x14ftypqt  \0\0\0\0"),
+            ("IMG_0001.AAE", b"<?xml"),
+            ("clip.avi", b"RIFF\0\0\0\0AVI LIST"),
+            ("2024/empty.jpg", b""),
+            ("2024/empty.m4v", b""),
+            ("2024/photo.xmp", b"<x:xmpmeta"),
+            ("2024/notes.txt", b"notes"),
+            // Magic bytes win over a wrong extension
+            ("2024/renamed.dat", b"\x89PNG\r\n\x1a\n\0\0"),
+        ];
+        for (name, content) in files {
+            fs::write(root.join(name), content).unwrap();
+        }
+
+        assert_eq!(classify_path(&root.join("IMG_0001.HEIC")), FileClass::Image);
+        assert_eq!(classify_path(&root.join("IMG_0001.MOV")), FileClass::Video);
+        assert_eq!(
+            classify_path(&root.join("IMG_0001.AAE")),
+            FileClass::Sidecar
+        );
+        assert_eq!(classify_path(&root.join("clip.avi")), FileClass::Video);
+        assert_eq!(
+            classify_path(&root.join("2024/empty.jpg")),
+            FileClass::Image
+        );
+        assert_eq!(
+            classify_path(&root.join("2024/photo.xmp")),
+            FileClass::Sidecar
+        );
+        assert_eq!(
+            classify_path(&root.join("2024/notes.txt")),
+            FileClass::Other
+        );
+        assert_eq!(classify_path(&root.join("missing.mp4")), FileClass::Video);
+
+        let partitioned = partition_directory(&root).unwrap();
+        assert_eq!(
+            partitioned,
+            PartitionedFiles {
+                images: vec![
+                    root.join("2024/empty.jpg"),
+                    root.join("2024/renamed.dat"),
+                    root.join("IMG_0001.HEIC"),
+                ],
+                videos: vec![
+                    root.join("2024/empty.m4v"),
+                    root.join("IMG_0001.MOV"),
+                    root.join("clip.avi"),
+                ],
+                other: vec![
+                    root.join("2024/notes.txt"),
+                    root.join("2024/photo.xmp"),
+                    root.join("IMG_0001.AAE"),
+                ],
+            }
+        );
+
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img");
+        assert_eq!(partition_directory(&fixtures).unwrap().images.len(), 2);
+    }
+}
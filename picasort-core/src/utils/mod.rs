@@ -1,2 +1,20 @@
+pub mod classify;
+pub mod motion;
+pub mod path;
+#[cfg(feature = "pixels")]
+pub mod pixels;
 pub mod sha;
 pub mod thumbnail;
+
+pub use classify::{FileClass, PartitionedFiles, classify_path, partition_directory};
+pub use motion::{MotionCompanion, has_motion_companion};
+pub use path::sanitize_path_component;
+#[cfg(feature = "pixels")]
+pub use pixels::{average_color, image_dimensions, mean_luminance, sharpness_score};
+pub use sha::{cas_path, hash_bytes, hash_bytes_with, jpeg_image_stream_hash, short_uuid};
+/// The RustCrypto trait taken by the `_with` hash functions, and their
+/// default algorithm, so that callers need not depend on `sha2` themselves
+pub use sha2::{Digest, Sha256};
+#[cfg(feature = "pixels")]
+pub use thumbnail::ThumbnailCache;
+pub use thumbnail::thumbnail_dimensions;
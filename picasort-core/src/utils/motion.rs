@@ -0,0 +1,206 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::metadata::xmp::{read_xmp_packet, xmp_property};
+
+/// Extensions of the video files, e.g. the MOV of an iPhone Live Photo
+pub const VIDEO_EXTENSIONS: [&str; 4] = ["mov", "mp4", "m4v", "avi"];
+
+/// XMP properties set to 1 by the Google motion photos embedding a video
+const MOTION_PHOTO_PROPERTIES: [&str; 2] = ["GCamera:MotionPhoto", "GCamera:MicroVideo"];
+
+/// Name of the video block in the trailer of the Samsung motion photos
+const SAMSUNG_MOTION_DATA: &[u8] = b"MotionPhoto_Data";
+
+/// Largest Samsung trailer directory read, a few entries in practice
+const SAMSUNG_TRAILER_MAX_SIZE: u64 = 64 * 1024;
+
+/// Video part of a motion photo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MotionCompanion {
+    /// Sibling video with the same stem, e.g. `IMG_0001.MOV` for `IMG_0001.HEIC`
+    Sibling(PathBuf),
+    /// Video appended to the image itself, as flagged by its XMP or its
+    /// Samsung trailer
+    Embedded,
+}
+
+/// Finds the video paired with the photo at `path`: a sibling file with the
+/// same stem (case insensitive) and a video extension, else a video embedded
+/// in the file. None for a plain photo.
+pub fn has_motion_companion(path: &Path) -> Option<MotionCompanion> {
+    sibling_video(path)
+        .map(MotionCompanion::Sibling)
+        .or_else(|| has_embedded_video(path).then_some(MotionCompanion::Embedded))
+}
+
+fn sibling_video(path: &Path) -> Option<PathBuf> {
+    let stem = path.file_stem()?.to_str()?;
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut videos: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|candidate| {
+            let same_stem = candidate
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|s| s.eq_ignore_ascii_case(stem));
+            let is_video = candidate
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| VIDEO_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+            same_stem && is_video && candidate.is_file()
+        })
+        .collect();
+    videos.sort();
+    videos.into_iter().next()
+}
+
+fn has_embedded_video(path: &Path) -> bool {
+    let flagged = read_xmp_packet(path).ok().flatten().is_some_and(|xmp| {
+        MOTION_PHOTO_PROPERTIES
+            .iter()
+            .any(|name| xmp_property(&xmp, name).is_some_and(|value| value.trim() == "1"))
+    });
+    flagged || has_samsung_motion_data(path).unwrap_or(false)
+}
+
+/// Looks for the video block in the Samsung trailer ending the file: blocks
+/// of data, then a directory starting with "SEFH", then the directory size
+/// and "SEFT". Each directory entry (type, offset back from the directory,
+/// size) points to a block starting with its name.
+fn has_samsung_motion_data(path: &Path) -> io::Result<bool> {
+    let u32_at = |bytes: &[u8], pos: usize| -> Option<u64> {
+        Some(u32::from_le_bytes(bytes.get(pos..pos + 4)?.try_into().ok()?) as u64)
+    };
+    let mut file = File::open(path)?;
+    let length = file.metadata()?.len();
+    if length < 8 {
+        return Ok(false);
+    }
+    let mut end = [0; 8];
+    file.seek(SeekFrom::End(-8))?;
+    file.read_exact(&mut end)?;
+    let size = u32_at(&end, 0).unwrap_or_default();
+    if &end[4..] != b"SEFT" || size > SAMSUNG_TRAILER_MAX_SIZE {
+        return Ok(false);
+    }
+    let Some(start) = length.checked_sub(8 + size) else {
+        return Ok(false);
+    };
+    let mut directory = vec![0; size as usize];
+    file.seek(SeekFrom::Start(start))?;
+    file.read_exact(&mut directory)?;
+    if !directory.starts_with(b"SEFH") {
+        return Ok(false);
+    }
+    let count = u32_at(&directory, 8).unwrap_or_default() as usize;
+    for entry in directory[12..].chunks_exact(12).take(count) {
+        let Some(block) = u32_at(entry, 4).and_then(|offset| start.checked_sub(offset)) else {
+            continue;
+        };
+        // Block header: padding, type, name length, name
+        let mut header = [0; 8 + SAMSUNG_MOTION_DATA.len()];
+        file.seek(SeekFrom::Start(block))?;
+        if file.read_exact(&mut header).is_ok()
+            && u32_at(&header, 4) == Some(SAMSUNG_MOTION_DATA.len() as u64)
+            && header[8..] == *SAMSUNG_MOTION_DATA
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn has_motion_companions() {
+        let root = TempDir::new();
+        fs::write(root.join("IMG_0001.HEIC"), b"").unwrap();
+        fs::write(root.join("IMG_0001.MOV"), b"").unwrap();
+        fs::write(root.join("IMG_0002.jpg"), b"").unwrap();
+        fs::write(root.join("IMG_0002.txt"), b"").unwrap();
+        fs::write(
+            root.join("PXL_0003.MP.jpg"),
+            b"\xFF\xD8<x:xmpmeta GCamera:MotionPhoto=\"1\"></x:xmpmeta>",
+        )
+        .unwrap();
+        fs::write(
+            root.join("PXL_0004.jpg"),
+            b"\xFF\xD8<x:xmpmeta><GCamera:MicroVideo>1</GCamera:MicroVideo></x:xmpmeta>",
+        )
+        .unwrap();
+        fs::write(
+            root.join("PXL_0005.jpg"),
+            b"\xFF\xD8<x:xmpmeta GCamera:MotionPhoto=\"0\"></x:xmpmeta>",
+        )
+        .unwrap();
+        // The video block lies beyond the XMP search window
+        fs::write(
+            root.join("20240101_0006.jpg"),
+            samsung_trailer(SAMSUNG_MOTION_DATA),
+        )
+        .unwrap();
+        fs::write(
+            root.join("20240101_0007.jpg"),
+            samsung_trailer(b"Image_UTC_Data"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            has_motion_companion(&root.join("IMG_0001.HEIC")),
+            Some(MotionCompanion::Sibling(root.join("IMG_0001.MOV")))
+        );
+        assert_eq!(has_motion_companion(&root.join("IMG_0002.jpg")), None);
+        assert_eq!(
+            has_motion_companion(&root.join("PXL_0003.MP.jpg")),
+            Some(MotionCompanion::Embedded)
+        );
+        assert_eq!(
+            has_motion_companion(&root.join("PXL_0004.jpg")),
+            Some(MotionCompanion::Embedded)
+        );
+        assert_eq!(has_motion_companion(&root.join("PXL_0005.jpg")), None);
+        assert_eq!(
+            has_motion_companion(&root.join("20240101_0006.jpg")),
+            Some(MotionCompanion::Embedded)
+        );
+        assert_eq!(has_motion_companion(&root.join("20240101_0007.jpg")), None);
+        assert_eq!(has_motion_companion(&root.join("missing.jpg")), None);
+
+        let fixture =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        assert_eq!(has_motion_companion(&fixture), None);
+    }
+
+    /// Image followed by a Samsung trailer with a single block named `name`
+    fn samsung_trailer(name: &[u8]) -> Vec<u8> {
+        let mut data = b"\xFF\xD8\xFF\xD9".to_vec();
+        let block = data.len();
+        data.extend_from_slice(&[0, 0, 0x30, 0x0a]);
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name);
+        data.extend_from_slice(&vec![0; 300 * 1024]);
+        let directory = data.len();
+        data.extend_from_slice(b"SEFH");
+        data.extend_from_slice(&106u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[0, 0, 0x30, 0x0a]);
+        data.extend_from_slice(&((directory - block) as u32).to_le_bytes());
+        data.extend_from_slice(&((directory - block) as u32).to_le_bytes());
+        data.extend_from_slice(&((data.len() - directory) as u32).to_le_bytes());
+        data.extend_from_slice(b"SEFT");
+        data
+    }
+}
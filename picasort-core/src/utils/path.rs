@@ -0,0 +1,93 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+/// Characters that cannot be used in a file name on at least one platform
+const ILLEGAL_CHARS: [char; 9] = ['/', '\\', ':', '<', '>', '|', '?', '*', '"'];
+
+/// Device names reserved by Windows, whatever the extension
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Tells if the name is a Windows reserved device name (e.g. `nul.jpg`)
+pub fn is_reserved_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end();
+    RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+}
+
+/// Makes a string (e.g. an EXIF camera model like `Canon/EOS`) safe to use as
+/// a single folder or file name: separators, characters illegal on Windows and
+/// control characters become `_`, trailing dots and spaces are trimmed.
+/// Windows reserved device names are prefixed with `_`.
+pub fn sanitize_path_component(s: &str) -> String {
+    let sanitized: String = s
+        .chars()
+        .map(|c| {
+            if ILLEGAL_CHARS.contains(&c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let sanitized = sanitized.trim_end_matches(['.', ' ']).trim_start();
+    if sanitized.is_empty() {
+        return "_".to_string();
+    }
+    if is_reserved_name(sanitized) {
+        return format!("_{sanitized}");
+    }
+    sanitized.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Canon/EOS", "Canon_EOS")]
+    #[case("a\\b:c<d>e|f?g*h\"i", "a_b_c_d_e_f_g_h_i")]
+    #[case("iPhone 12 Pro", "iPhone 12 Pro")]
+    #[case("NIKON\0", "NIKON_")]
+    #[case("  name. . ", "name")]
+    #[case("...", "_")]
+    #[case("", "_")]
+    #[case("Élise", "Élise")]
+    fn has_sanitized_component(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(sanitize_path_component(input), expected);
+    }
+
+    #[rstest]
+    fn has_reserved_names_prefixed(
+        #[values(
+            "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7",
+            "COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9"
+        )]
+        name: &str,
+        #[values("", ".jpg", ".tar.gz")] extension: &str,
+        #[values(false, true)] lowercase: bool,
+    ) {
+        let name = if lowercase {
+            name.to_lowercase()
+        } else {
+            name.to_string()
+        };
+        let file_name = format!("{name}{extension}");
+        assert!(is_reserved_name(&file_name));
+        assert_eq!(sanitize_path_component(&file_name), format!("_{file_name}"));
+    }
+
+    #[rstest]
+    #[case("CONSOLE.jpg")]
+    #[case("COM0.jpg")]
+    #[case("LPT10")]
+    #[case("my_nul.jpg")]
+    fn has_non_reserved_names(#[case] name: &str) {
+        assert!(!is_reserved_name(name));
+        assert_eq!(sanitize_path_component(name), name);
+    }
+}
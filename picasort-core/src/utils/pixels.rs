@@ -0,0 +1,182 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+// Measures computed on the decoded pixels, on a downscaled copy of the image
+// to stay fast on large files.
+
+use std::path::Path;
+
+use image::DynamicImage;
+
+use crate::error::CoreError;
+
+/// Largest side of the downscaled copy used for the color measures
+const COLOR_SAMPLE_SIZE: u32 = 32;
+
+/// Largest side of the downscaled copy used for the luminance
+const LUMINANCE_SAMPLE_SIZE: u32 = 64;
+/// Largest side of the downscaled copy used for the sharpness
+const SHARPNESS_SAMPLE_SIZE: u32 = 512;
+/// Mean luminance under which an image is considered underexposed
+pub const UNDEREXPOSED_LUMINANCE: f64 = 0.15;
+/// Mean luminance over which an image is considered overexposed
+pub const OVEREXPOSED_LUMINANCE: f64 = 0.85;
+
+/// Brightness class of an image from its mean luminance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExposureLevel {
+    Under,
+    Normal,
+    Over,
+}
+
+impl ExposureLevel {
+    pub fn from_luminance(luminance: f64) -> ExposureLevel {
+        if luminance < UNDEREXPOSED_LUMINANCE {
+            ExposureLevel::Under
+        } else if luminance > OVEREXPOSED_LUMINANCE {
+            ExposureLevel::Over
+        } else {
+            ExposureLevel::Normal
+        }
+    }
+}
+
+/// Decodes the image and downscales it to fit in `size` x `size`, smaller
+/// images are kept as is
+pub(crate) fn decode_downscaled(path: &Path, size: u32) -> Result<DynamicImage, CoreError> {
+    let image = image::open(path)?;
+    if image.width() <= size && image.height() <= size {
+        return Ok(image);
+    }
+    Ok(image.thumbnail(size, size))
+}
+
+/// Width and height of an encoded image, read from its header without
+/// decoding the pixels
+pub fn image_dimensions(data: &[u8]) -> Result<(u32, u32), CoreError> {
+    let reader = image::ImageReader::new(std::io::Cursor::new(data)).with_guessed_format()?;
+    Ok(reader.into_dimensions()?)
+}
+
+/// Average RGB color of the image, alpha is ignored
+pub fn average_color<P: AsRef<Path>>(path: P) -> Result<[u8; 3], CoreError> {
+    let image = decode_downscaled(path.as_ref(), COLOR_SAMPLE_SIZE)?.to_rgb8();
+    let count = (image.width() * image.height()).max(1) as u64;
+    let mut sums = [0u64; 3];
+    for pixel in image.pixels() {
+        for (sum, channel) in sums.iter_mut().zip(pixel.0) {
+            *sum += channel as u64;
+        }
+    }
+    Ok(sums.map(|sum| (sum as f64 / count as f64).round() as u8))
+}
+
+/// Rec. 709 luma of the gamma encoded channels, from 0.0 to 1.0
+fn luma([r, g, b]: [u8; 3]) -> f64 {
+    (0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64) / 255.0
+}
+
+/// Average Rec. 709 luma of the image, from 0.0 (black) to 1.0 (white)
+pub fn mean_luminance<P: AsRef<Path>>(path: P) -> Result<f64, CoreError> {
+    let image = decode_downscaled(path.as_ref(), LUMINANCE_SAMPLE_SIZE)?.to_rgb8();
+    let count = (image.width() * image.height()).max(1) as f64;
+    Ok(image.pixels().map(|p| luma(p.0)).sum::<f64>() / count)
+}
+
+/// Variance of the Laplacian of the grayscale image, higher is sharper.
+///
+/// The score depends on the content and on the size of the image: it only
+/// ranks similar images, e.g. the frames of a burst, to keep the sharpest.
+pub fn sharpness_score<P: AsRef<Path>>(path: P) -> Result<f64, CoreError> {
+    let image = decode_downscaled(path.as_ref(), SHARPNESS_SAMPLE_SIZE)?.to_luma8();
+    let (width, height) = image.dimensions();
+    if width < 3 || height < 3 {
+        return Ok(0.0);
+    }
+    let at = |x: u32, y: u32| image.get_pixel(x, y).0[0] as f64;
+    let laplacians: Vec<f64> = (1..height - 1)
+        .flat_map(|y| (1..width - 1).map(move |x| (x, y)))
+        .map(|(x, y)| at(x - 1, y) + at(x + 1, y) + at(x, y - 1) + at(x, y + 1) - 4.0 * at(x, y))
+        .collect();
+    let count = laplacians.len() as f64;
+    let mean = laplacians.iter().sum::<f64>() / count;
+    Ok(laplacians.iter().map(|l| (l - mean).powi(2)).sum::<f64>() / count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use image::{Rgba, RgbaImage};
+    use rstest::rstest;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_image(image: &RgbaImage) -> (TempDir, PathBuf) {
+        let root = TempDir::new();
+        let path = root.join("image.png");
+        image.save(&path).unwrap();
+        (root, path)
+    }
+
+    #[test]
+    fn has_average_color() {
+        // Half red, half blue, with a transparent alpha to be ignored
+        let image = RgbaImage::from_fn(100, 60, |x, _| match x < 50 {
+            true => Rgba([200, 0, 0, 0]),
+            false => Rgba([0, 0, 100, 255]),
+        });
+        let (_root, path) = temp_image(&image);
+        assert_eq!(average_color(&path).unwrap(), [100, 0, 50]);
+
+        let fixture =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        assert!(average_color(fixture).is_ok());
+        assert!(average_color("missing.jpg").is_err());
+    }
+
+    #[test]
+    fn has_image_dimensions() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img/text_car_animal_no-gps.png");
+        let data = fs::read(fixture).unwrap();
+        assert_eq!(image_dimensions(&data).unwrap(), (1024, 769));
+        assert!(image_dimensions(b"not an image").is_err());
+    }
+
+    #[rstest]
+    #[case([0, 0, 0], 0.0, ExposureLevel::Under)]
+    #[case([255, 255, 255], 1.0, ExposureLevel::Over)]
+    #[case([0, 255, 0], 0.7152, ExposureLevel::Normal)]
+    fn has_mean_luminance(
+        #[case] color: [u8; 3],
+        #[case] expected: f64,
+        #[case] level: ExposureLevel,
+    ) {
+        let [r, g, b] = color;
+        let (_root, path) = temp_image(&RgbaImage::from_pixel(80, 80, Rgba([r, g, b, 255])));
+        let luminance = mean_luminance(&path).unwrap();
+        assert!((luminance - expected).abs() < 1e-6, "{luminance}");
+        assert_eq!(ExposureLevel::from_luminance(luminance), level);
+    }
+
+    #[test]
+    fn has_sharpness_score() {
+        let score = |image: RgbaImage| {
+            let (_root, path) = temp_image(&image);
+            sharpness_score(&path).unwrap()
+        };
+        let checkerboard = score(RgbaImage::from_fn(64, 64, |x, y| {
+            let v = if (x / 2 + y / 2) % 2 == 0 { 0 } else { 255 };
+            Rgba([v, v, v, 255])
+        }));
+        let gradient = score(RgbaImage::from_fn(64, 64, |x, _| {
+            let v = (x * 4) as u8;
+            Rgba([v, v, v, 255])
+        }));
+        let flat = score(RgbaImage::from_pixel(64, 64, Rgba([90, 90, 90, 255])));
+        assert!(checkerboard > gradient, "{checkerboard} <= {gradient}");
+        assert_eq!(flat, 0.0);
+    }
+}
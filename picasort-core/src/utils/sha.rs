@@ -1,26 +1,194 @@
 use sha2::{Digest, Sha256};
-use std::fs::File;
+use std::fmt::Write;
+use std::fs::{self, File};
 use std::io::{BufReader, Read};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::error::CoreError;
+use crate::metadata::format::{JPEG_SOS, JpegSegment, jpeg_segments};
 
 pub fn get_file_uuid<P: AsRef<Path>>(path: P) -> Result<String, CoreError> {
+    get_file_uuid_with::<Sha256, P>(path)
+}
+
+/// Same as `get_file_uuid` with the hash algorithm `D`, any implementation
+/// of the RustCrypto `Digest` trait, e.g. `sha1::Sha1` for git interop
+pub fn get_file_uuid_with<D: Digest, P: AsRef<Path>>(path: P) -> Result<String, CoreError> {
+    get_file_digest_with::<D, P>(path).map(|(hash, _)| hash)
+}
+
+/// Hex SHA-256 of an in-memory buffer, equal to `get_file_uuid` of a file
+/// holding the same bytes
+pub fn hash_bytes(data: &[u8]) -> String {
+    hash_bytes_with::<Sha256>(data)
+}
+
+/// Same as `hash_bytes` with the hash algorithm `D`
+pub fn hash_bytes_with<D: Digest>(data: &[u8]) -> String {
+    to_hex(&D::digest(data))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(2 * bytes.len()), |mut hex, b| {
+            // Writing to a String cannot fail
+            let _ = write!(hex, "{b:02x}");
+            hex
+        })
+}
+
+/// First `len` characters of a hex uuid, as a short handle for display or
+/// file names. None when `full` is not hex or is shorter than `len`.
+///
+/// Shorter handles collide sooner. For a 1% (50%) chance that two photos share
+/// a handle, a library needs about:
+/// - 8 characters (32 bits): 9 300 (77 000) photos
+/// - 12 characters (48 bits): 2.4 million (20 million) photos
+/// - 16 characters (64 bits): 610 million (5 billion) photos
+pub fn short_uuid(full: &str, len: usize) -> Option<&str> {
+    if !full.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    full.get(..len)
+}
+
+/// Content-addressable path of a file, as git lays out its objects: the
+/// first two characters of the hex uuid as folder, the rest with the
+/// extension as name, e.g. `ab/cdef0123.jpg`. The uuid and the extension are
+/// lowercased so that identical contents get the same path. None when `uuid`
+/// is not hex or has less than 3 characters.
+pub fn cas_path(uuid: &str, ext: &str) -> Option<PathBuf> {
+    if uuid.len() < 3 || !uuid.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let uuid = uuid.to_ascii_lowercase();
+    let (fanout, name) = uuid.split_at(2);
+    let mut path = Path::new(fanout).join(name);
+    let ext = ext.trim_start_matches('.');
+    if !ext.is_empty() {
+        path.set_extension(ext.to_lowercase());
+    }
+    Some(path)
+}
+
+/// Hex SHA-256 of the compressed image data of a JPEG, from the first start
+/// of scan to the end of image marker. Metadata segments and data appended
+/// after the image are left out, so editing the EXIF keeps the same hash.
+pub fn jpeg_image_stream_hash<P: AsRef<Path>>(path: P) -> Result<String, CoreError> {
+    let path = path.as_ref();
+    let data = fs::read(path)?;
+    let stream = jpeg_image_stream(&data)
+        .ok_or_else(|| CoreError::InvalidJpeg(path.display().to_string()))?;
+    Ok(hash_bytes(stream))
+}
+
+/// Bytes from the first SOS marker to the EOI marker included
+fn jpeg_image_stream(data: &[u8]) -> Option<&[u8]> {
+    let scan = jpeg_segments(data).find(|s| s.marker == JPEG_SOS)?;
+    let start = scan.start;
+    let mut pos = scan.end;
+    // Entropy coded data: 0xFF is only followed by a stuffed 0x00, a restart
+    // marker or fill bytes, any other marker (tables, next scan) is a segment
+    while pos + 1 < data.len() {
+        match (data[pos], data[pos + 1]) {
+            (0xFF, 0xD9) => return Some(&data[start..pos + 2]),
+            (0xFF, 0x00 | 0xD0..=0xD7 | 0xFF) => pos += 1,
+            (0xFF, _) => pos = JpegSegment::at(data, pos)?.end,
+            _ => pos += 1,
+        }
+    }
+    None
+}
+
+/// Returns the SHA-256 of the file and its size, read in a single pass
+pub fn get_file_digest<P: AsRef<Path>>(path: P) -> Result<(String, u64), CoreError> {
+    get_file_digest_with::<Sha256, P>(path)
+}
+
+/// Same as `get_file_digest` with the hash algorithm `D`
+pub fn get_file_digest_with<D: Digest, P: AsRef<Path>>(
+    path: P,
+) -> Result<(String, u64), CoreError> {
     let file = File::open(path)?;
-    let mut reader = BufReader::new(file);
+    digest_reader_with::<D, _>(&[], BufReader::new(file))
+}
+
+/// SHA-256 and size of `prefix` followed by the content of `reader`, for
+/// files whose start was already read
+pub(crate) fn digest_reader<R: Read>(prefix: &[u8], reader: R) -> Result<(String, u64), CoreError> {
+    digest_reader_with::<Sha256, R>(prefix, reader)
+}
+
+/// Same as `digest_reader` with the hash algorithm `D`
+fn digest_reader_with<D: Digest, R: Read>(
+    prefix: &[u8],
+    mut reader: R,
+) -> Result<(String, u64), CoreError> {
+    let mut hasher = D::new();
+    hasher.update(prefix);
+    let mut buffer = [0; 8192];
+    let mut size = prefix.len() as u64;
+
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+        size += bytes_read as u64;
+    }
+
+    Ok((to_hex(&hasher.finalize()), size))
+}
+
+/// Same as [`get_file_digest`], reading the file with tokio
+#[cfg(feature = "tokio")]
+pub async fn get_file_digest_async<P: AsRef<Path>>(path: P) -> Result<(String, u64), CoreError> {
+    get_file_digest_async_with::<Sha256, P>(path).await
+}
+
+/// Same as [`get_file_digest_async`] with the hash algorithm `D`
+#[cfg(feature = "tokio")]
+pub async fn get_file_digest_async_with<D: Digest, P: AsRef<Path>>(
+    path: P,
+) -> Result<(String, u64), CoreError> {
+    let file = tokio::fs::File::open(path).await?;
+    digest_reader_async_with::<D, _>(&[], file).await
+}
 
-    let mut hasher = Sha256::new();
+/// Same as [`digest_reader`] for an asynchronous reader
+#[cfg(feature = "tokio")]
+pub(crate) async fn digest_reader_async<R: tokio::io::AsyncRead + Unpin>(
+    prefix: &[u8],
+    reader: R,
+) -> Result<(String, u64), CoreError> {
+    digest_reader_async_with::<Sha256, R>(prefix, reader).await
+}
+
+/// Same as `digest_reader_async` with the hash algorithm `D`
+#[cfg(feature = "tokio")]
+async fn digest_reader_async_with<D: Digest, R: tokio::io::AsyncRead + Unpin>(
+    prefix: &[u8],
+    mut reader: R,
+) -> Result<(String, u64), CoreError> {
+    use tokio::io::AsyncReadExt;
+
+    let mut hasher = D::new();
+    hasher.update(prefix);
     let mut buffer = [0; 8192];
+    let mut size = prefix.len() as u64;
 
-    while let Ok(bytes_read) = reader.read(&mut buffer) {
+    loop {
+        let bytes_read = reader.read(&mut buffer).await?;
         if bytes_read == 0 {
             break;
         }
         hasher.update(&buffer[..bytes_read]);
+        size += bytes_read as u64;
     }
 
-    let hash_result = hasher.finalize();
-    Ok(format!("{:x}", hash_result))
+    Ok((to_hex(&hasher.finalize()), size))
 }
 
 #[cfg(test)]
@@ -57,4 +225,101 @@ mod tests {
         }
         Ok(())
     }
+
+    #[rstest]
+    #[case("text_icon_gps.jpg")]
+    #[case("text_car_animal_no-gps.png")]
+    fn has_file_digest(#[case] filename: &str) {
+        use std::path::Path;
+        let image_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img")
+            .join(filename);
+        let (hash, size) = get_file_digest(&image_path).unwrap();
+        assert_eq!(hash, get_file_uuid(&image_path).unwrap());
+        assert_eq!(size, std::fs::metadata(&image_path).unwrap().len());
+    }
+
+    #[rstest]
+    #[case("text_icon_gps.jpg")]
+    #[case("text_car_animal_no-gps.png")]
+    fn has_bytes_hash(#[case] filename: &str) {
+        use std::path::Path;
+        let image_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources/img")
+            .join(filename);
+        let data = std::fs::read(&image_path).unwrap();
+        assert_eq!(hash_bytes(&data), get_file_uuid(&image_path).unwrap());
+        assert_eq!(
+            hash_bytes(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn has_generic_digest() {
+        use sha2::Sha512;
+        use std::path::Path;
+
+        let image_path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img/text_icon_gps.jpg");
+        assert_eq!(
+            hash_bytes_with::<Sha512>(b""),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+             47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        );
+        let (hash, size) = get_file_digest_with::<Sha512, _>(&image_path).unwrap();
+        let data = fs::read(&image_path).unwrap();
+        assert_eq!(hash, hash_bytes_with::<Sha512>(&data));
+        assert_eq!(size, data.len() as u64);
+        assert_eq!(
+            get_file_uuid_with::<Sha256, _>(&image_path).unwrap(),
+            get_file_uuid(&image_path).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("75f5e4ce87df5e44", 8, Some("75f5e4ce"))]
+    #[case("75f5e4ce", 8, Some("75f5e4ce"))]
+    #[case("75f5e4", 8, None)]
+    #[case("75f5e4ce-87df", 8, None)]
+    #[case("", 0, Some(""))]
+    fn has_short_uuid(#[case] full: &str, #[case] len: usize, #[case] expected: Option<&str>) {
+        assert_eq!(short_uuid(full, len), expected);
+    }
+
+    #[rstest]
+    #[case("75f5e4ce87df", "jpg", Some("75/f5e4ce87df.jpg"))]
+    #[case("75F5E4CE87DF", ".JPG", Some("75/f5e4ce87df.jpg"))]
+    #[case("75f", "", Some("75/f"))]
+    #[case("75", "jpg", None)]
+    #[case("75f5e4ce-87df", "jpg", None)]
+    #[case("../../etc", "jpg", None)]
+    fn has_cas_path(#[case] uuid: &str, #[case] ext: &str, #[case] expected: Option<&str>) {
+        assert_eq!(cas_path(uuid, ext), expected.map(PathBuf::from));
+    }
+
+    #[test]
+    fn has_jpeg_image_stream_hash() {
+        use std::path::Path;
+        let image_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img");
+        let jpeg = image_path.join("text_icon_gps.jpg");
+        let hash = jpeg_image_stream_hash(&jpeg).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_ne!(hash, get_file_uuid(&jpeg).unwrap());
+
+        // Same image data behind edited metadata and a trailer
+        let mut data = fs::read(&jpeg).unwrap();
+        let stream = jpeg_image_stream(&data).unwrap().to_vec();
+        data.extend_from_slice(b"trailer");
+        assert_eq!(jpeg_image_stream(&data), Some(stream.as_slice()));
+        let mut edited = vec![0xFF, 0xD8, 0xFF, 0xFE, 0x00, 0x06, b'e', b'd', b'i', b't'];
+        edited.extend_from_slice(&stream);
+        assert_eq!(jpeg_image_stream(&edited), Some(stream.as_slice()));
+        assert_eq!(jpeg_image_stream(&edited[..edited.len() - 2]), None);
+
+        assert!(matches!(
+            jpeg_image_stream_hash(image_path.join("text_car_animal_no-gps.png")),
+            Err(CoreError::InvalidJpeg(_))
+        ));
+    }
 }
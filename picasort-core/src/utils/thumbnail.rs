@@ -1,4 +1,14 @@
+#[cfg(feature = "pixels")]
+use std::fs;
+#[cfg(feature = "pixels")]
+use std::path::{Path, PathBuf};
+
+use little_exif::{exif_tag::ExifTag, ifd::ExifTagGroup};
+
 use crate::error::CoreError;
+use crate::metadata::format::jpeg_segments;
+#[cfg(feature = "pixels")]
+use crate::utils::{pixels::decode_downscaled, sha::get_file_uuid};
 
 pub struct ThumbnailInfo<'a> {
     _file_path: &'a str,
@@ -8,3 +18,176 @@ pub struct ThumbnailInfo<'a> {
 pub fn generate_thumbnails() -> Result<(), CoreError> {
     Ok(())
 }
+
+/// Thumbnails stored in a directory as "<content hash>_<size>.jpg". A file
+/// whose content changes gets a new hash, so stale thumbnails are never
+/// returned.
+#[cfg(feature = "pixels")]
+#[derive(Debug, Clone)]
+pub struct ThumbnailCache {
+    dir: PathBuf,
+}
+
+#[cfg(feature = "pixels")]
+impl ThumbnailCache {
+    /// Cache in `dir`, created when missing
+    pub fn new<P: AsRef<Path>>(dir: P) -> Result<ThumbnailCache, CoreError> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(ThumbnailCache {
+            dir: dir.as_ref().to_path_buf(),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the thumbnail of a file with the content hash `hash`, fitting
+    /// in `size` x `size`
+    pub fn thumbnail_path(&self, hash: &str, size: u32) -> PathBuf {
+        self.dir.join(format!("{hash}_{size}.jpg"))
+    }
+
+    /// Path of the cached thumbnail of the image at `path`, generated and
+    /// stored first when missing. Images smaller than `size` are kept at
+    /// their size.
+    pub fn get_or_generate<P: AsRef<Path>>(
+        &self,
+        path: P,
+        size: u32,
+    ) -> Result<PathBuf, CoreError> {
+        let thumbnail_path = self.thumbnail_path(&get_file_uuid(path.as_ref())?, size);
+        if thumbnail_path.is_file() {
+            return Ok(thumbnail_path);
+        }
+        let thumbnail = decode_downscaled(path.as_ref(), size)?.to_rgb8();
+        // Written aside then renamed, a concurrent reader never sees a
+        // partial thumbnail
+        let partial = thumbnail_path.with_extension(format!("{}.part", uuid::Uuid::new_v4()));
+        let stored = thumbnail
+            .save_with_format(&partial, image::ImageFormat::Jpeg)
+            .map_err(CoreError::from)
+            .and_then(|_| Ok(fs::rename(&partial, &thumbnail_path)?));
+        if let Err(e) = stored {
+            // The save may fail before creating the file
+            let _ = fs::remove_file(&partial);
+            return Err(e);
+        }
+        Ok(thumbnail_path)
+    }
+}
+
+/// Width and height of the embedded EXIF thumbnail, from the IFD1 ImageWidth
+/// and ImageLength tags or else from the header of the thumbnail JPEG. None
+/// when the file has no thumbnail.
+pub fn thumbnail_dimensions(metadata: &little_exif::metadata::Metadata) -> Option<(u32, u32)> {
+    let ifd = metadata.get_ifd(ExifTagGroup::GENERIC, 1)?;
+    let (mut width, mut height, mut jpeg) = (None, None, None);
+    for tag in ifd.get_tags() {
+        match tag {
+            ExifTag::ImageWidth(v) => width = v.first().copied(),
+            ExifTag::ImageHeight(v) => height = v.first().copied(),
+            ExifTag::ThumbnailOffset(_, data) if !data.is_empty() => jpeg = Some(data),
+            _ => (),
+        }
+    }
+    match (width, height) {
+        (Some(width), Some(height)) => Some((width, height)),
+        _ => jpeg_dimensions(jpeg?),
+    }
+}
+
+/// Reads the width and height of a JPEG from its start of frame segment
+fn jpeg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    // SOF0 to SOF15, except DHT (C4), JPG (C8) and DAC (CC)
+    let frame = jpeg_segments(data)
+        .find(|s| matches!(s.marker, 0xC0..=0xCF) && !matches!(s.marker, 0xC4 | 0xC8 | 0xCC))?;
+    let [_precision, h0, h1, w0, w1, ..] = frame.body else {
+        return None;
+    };
+    let height = u16::from_be_bytes([*h0, *h1]) as u32;
+    let width = u16::from_be_bytes([*w0, *w1]) as u32;
+    Some((width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use little_exif::metadata::Metadata;
+    use std::path::Path;
+
+    #[test]
+    fn has_thumbnail_dimensions() {
+        let image_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/img");
+        for filename in ["text_icon_gps.jpg", "text_car_animal_no-gps.png"] {
+            let metadata = Metadata::new_from_path(&image_path.join(filename)).unwrap();
+            assert_eq!(thumbnail_dimensions(&metadata), None);
+        }
+
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::ImageWidth(vec![4000]));
+        let ifd = metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1);
+        let thumbnail = vec![
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x0B, 0x08, 0x00,
+            0x78, 0x00, 0xA0, 0x01, 0x01, 0x11, 0x00,
+        ];
+        ifd.set_tag(ExifTag::ThumbnailOffset(Vec::new(), thumbnail));
+        assert_eq!(thumbnail_dimensions(&metadata), Some((160, 120)));
+
+        let ifd = metadata.get_ifd_mut(ExifTagGroup::GENERIC, 1);
+        ifd.set_tag(ExifTag::ImageWidth(vec![320]));
+        ifd.set_tag(ExifTag::ImageHeight(vec![240]));
+        assert_eq!(thumbnail_dimensions(&metadata), Some((320, 240)));
+    }
+
+    #[cfg(feature = "pixels")]
+    #[test]
+    fn has_thumbnail_cache() {
+        use crate::test_utils::TempDir;
+        use image::{Rgb, RgbImage};
+
+        let root = TempDir::new();
+        let cache = ThumbnailCache::new(root.join("cache")).unwrap();
+        let path = root.join("image.png");
+        RgbImage::from_pixel(400, 200, Rgb([200, 0, 0]))
+            .save(&path)
+            .unwrap();
+
+        let thumbnail = cache.get_or_generate(&path, 100).unwrap();
+        assert_eq!(thumbnail.parent(), Some(cache.dir()));
+        assert_eq!(image::image_dimensions(&thumbnail).unwrap(), (100, 50));
+        assert_eq!(cache.get_or_generate(&path, 100).unwrap(), thumbnail);
+        let small = cache.get_or_generate(&path, 1000).unwrap();
+        assert_ne!(small, thumbnail);
+        assert_eq!(image::image_dimensions(&small).unwrap(), (400, 200));
+
+        // Cached thumbnails are returned as is, without decoding the image
+        fs::write(&thumbnail, b"cached").unwrap();
+        assert_eq!(cache.get_or_generate(&path, 100).unwrap(), thumbnail);
+        assert_eq!(fs::read(&thumbnail).unwrap(), b"cached");
+
+        RgbImage::from_pixel(200, 400, Rgb([0, 0, 200]))
+            .save(&path)
+            .unwrap();
+        let edited = cache.get_or_generate(&path, 100).unwrap();
+        assert_ne!(edited, thumbnail);
+        assert_eq!(image::image_dimensions(&edited).unwrap(), (50, 100));
+
+        assert!(
+            cache
+                .get_or_generate(root.join("missing.png"), 100)
+                .is_err()
+        );
+        assert_eq!(fs::read_dir(cache.dir()).unwrap().count(), 3);
+
+        // The rename onto a directory fails, the partial file is removed
+        let blocked = root.join("blocked.png");
+        RgbImage::from_pixel(40, 20, Rgb([0, 200, 0]))
+            .save(&blocked)
+            .unwrap();
+        let hash = get_file_uuid(&blocked).unwrap();
+        fs::create_dir(cache.thumbnail_path(&hash, 100)).unwrap();
+        assert!(cache.get_or_generate(&blocked, 100).is_err());
+        assert_eq!(fs::read_dir(cache.dir()).unwrap().count(), 4);
+    }
+}
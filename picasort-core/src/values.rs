@@ -0,0 +1,475 @@
+// Copyright (c) 2026 Lemur-Catta.org
+// Author: Sylvain Gubian <sgubian@lemur-catta.org>
+
+// Pure value types and conversions, usable without `std` (e.g. WASM or
+// embedded targets) by disabling the default `std` feature.
+
+const EARTH_RADIUS_M: f64 = 6_371_008.8;
+const WEB_MERCATOR_RADIUS_M: f64 = 6_378_137.0;
+/// Relative difference of the sides under which an image is square
+const SQUARE_TOLERANCE: f64 = 0.05;
+/// Latitude making the Web Mercator world square
+pub const WEB_MERCATOR_MAX_LATITUDE: f64 = 85.051_128_78;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    Normal,
+    FlippedHorizontally,
+    Rotated180Deg,
+    FlippedVertically,
+    Rotated90DegCCWFlippedVertically,
+    Rotated90DegCW,
+    Rotated90DegCCWPFlippedHorizontally,
+    Rotated90DegCCW,
+    /// Any code outside 1..=8, handled as `Normal`: the image is left as stored
+    Unknown,
+}
+
+/// Operations turning the stored pixels upright: the horizontal flip is
+/// applied first, then the clockwise rotation
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct OrientationTransform {
+    pub flip_horizontal: bool,
+    /// Clockwise rotation in degrees: 0, 90, 180 or 270
+    pub rotate_cw: u16,
+}
+
+impl OrientationTransform {
+    pub const IDENTITY: OrientationTransform = OrientationTransform {
+        flip_horizontal: false,
+        rotate_cw: 0,
+    };
+
+    pub fn is_identity(&self) -> bool {
+        *self == OrientationTransform::IDENTITY
+    }
+}
+
+impl Orientation {
+    pub fn from_code(code: u16) -> Orientation {
+        match code {
+            1 => Orientation::Normal,
+            2 => Orientation::FlippedHorizontally,
+            3 => Orientation::Rotated180Deg,
+            4 => Orientation::FlippedVertically,
+            5 => Orientation::Rotated90DegCCWFlippedVertically,
+            6 => Orientation::Rotated90DegCW,
+            7 => Orientation::Rotated90DegCCWPFlippedHorizontally,
+            8 => Orientation::Rotated90DegCCW,
+            _ => Orientation::Unknown,
+        }
+    }
+
+    // Here, the function takes ownership
+    pub fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// Tells if the image is displayed rotated by 90°, width and height swapped
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(
+            self,
+            Orientation::Rotated90DegCCWFlippedVertically
+                | Orientation::Rotated90DegCW
+                | Orientation::Rotated90DegCCWPFlippedHorizontally
+                | Orientation::Rotated90DegCCW
+        )
+    }
+
+    /// Flip and rotation displaying the image upright, `Unknown` is the identity
+    pub fn transform(self) -> OrientationTransform {
+        let (flip_horizontal, rotate_cw) = match self {
+            Orientation::Normal | Orientation::Unknown => (false, 0),
+            Orientation::FlippedHorizontally => (true, 0),
+            Orientation::Rotated180Deg => (false, 180),
+            Orientation::FlippedVertically => (true, 180),
+            Orientation::Rotated90DegCCWFlippedVertically => (true, 270),
+            Orientation::Rotated90DegCW => (false, 90),
+            Orientation::Rotated90DegCCWPFlippedHorizontally => (true, 90),
+            Orientation::Rotated90DegCCW => (false, 270),
+        };
+        OrientationTransform {
+            flip_horizontal,
+            rotate_cw,
+        }
+    }
+}
+
+/// Kind of scene the camera was set to shoot (EXIF SceneCaptureType)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SceneCaptureType {
+    Standard,
+    Landscape,
+    Portrait,
+    Night,
+    Unknown,
+}
+
+impl SceneCaptureType {
+    pub fn from_code(code: u16) -> SceneCaptureType {
+        match code {
+            0 => SceneCaptureType::Standard,
+            1 => SceneCaptureType::Landscape,
+            2 => SceneCaptureType::Portrait,
+            3 => SceneCaptureType::Night,
+            _ => SceneCaptureType::Unknown,
+        }
+    }
+}
+
+/// Exposure mode set on the camera (EXIF ExposureProgram)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExposureProgram {
+    NotDefined,
+    Manual,
+    Normal,
+    AperturePriority,
+    ShutterPriority,
+    Creative,
+    Action,
+    Portrait,
+    Landscape,
+    Unknown,
+}
+
+impl ExposureProgram {
+    pub fn from_code(code: u16) -> ExposureProgram {
+        match code {
+            0 => ExposureProgram::NotDefined,
+            1 => ExposureProgram::Manual,
+            2 => ExposureProgram::Normal,
+            3 => ExposureProgram::AperturePriority,
+            4 => ExposureProgram::ShutterPriority,
+            5 => ExposureProgram::Creative,
+            6 => ExposureProgram::Action,
+            7 => ExposureProgram::Portrait,
+            8 => ExposureProgram::Landscape,
+            _ => ExposureProgram::Unknown,
+        }
+    }
+}
+
+/// White balance mode of the shot (EXIF WhiteBalance)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WhiteBalance {
+    Auto,
+    Manual,
+    Unknown,
+}
+
+impl WhiteBalance {
+    pub fn from_code(code: u16) -> WhiteBalance {
+        match code {
+            0 => WhiteBalance::Auto,
+            1 => WhiteBalance::Manual,
+            _ => WhiteBalance::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GPSCoord {
+    pub deg: usize,
+    pub min: usize,
+    pub sec: f64,
+}
+
+impl GPSCoord {
+    /// Converts the degrees, minutes, seconds to decimal degrees (unsigned)
+    pub fn to_decimal(&self) -> f64 {
+        self.deg as f64 + self.min as f64 / 60.0 + self.sec / 3600.0
+    }
+
+    /// Splits unsigned decimal degrees in degrees, minutes, seconds
+    pub fn from_decimal(value: f64) -> GPSCoord {
+        let value = libm::fabs(value);
+        let deg = libm::floor(value);
+        let minutes = (value - deg) * 60.0;
+        let min = libm::floor(minutes);
+        GPSCoord {
+            deg: deg as usize,
+            min: min as usize,
+            sec: (minutes - min) * 60.0,
+        }
+    }
+}
+
+/// Half of the globe, seasons are reversed between the two
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hemisphere {
+    North,
+    South,
+}
+
+/// Meteorological season, three whole months each
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// Season of a month (1 to 12), None for an invalid month
+    pub fn from_month(month: u32, hemisphere: Hemisphere) -> Option<Season> {
+        let north = match month {
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            9..=11 => Season::Autumn,
+            12 | 1 | 2 => Season::Winter,
+            _ => return None,
+        };
+        Some(match hemisphere {
+            Hemisphere::North => north,
+            Hemisphere::South => match north {
+                Season::Spring => Season::Autumn,
+                Season::Summer => Season::Winter,
+                Season::Autumn => Season::Spring,
+                Season::Winter => Season::Summer,
+            },
+        })
+    }
+}
+
+/// Part of the day: morning from 5h, afternoon from 12h, evening from 17h
+/// and night from 21h
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DayPart {
+    Morning,
+    Afternoon,
+    Evening,
+    Night,
+}
+
+impl DayPart {
+    /// Part of the day of an hour (0 to 23), None for an invalid hour
+    pub fn from_hour(hour: u32) -> Option<DayPart> {
+        match hour {
+            5..=11 => Some(DayPart::Morning),
+            12..=16 => Some(DayPart::Afternoon),
+            17..=20 => Some(DayPart::Evening),
+            21..=23 | 0..=4 => Some(DayPart::Night),
+            _ => None,
+        }
+    }
+}
+
+/// Shape of an image as displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Layout {
+    Portrait,
+    Landscape,
+    /// Sides within 5% of each other
+    Square,
+}
+
+impl Layout {
+    /// Layout of the displayed width and height, None when one is 0
+    pub fn from_dimensions(width: usize, height: usize) -> Option<Layout> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let (long, short) = (width.max(height) as f64, width.min(height) as f64);
+        Some(if long - short <= long * SQUARE_TOLERANCE {
+            Layout::Square
+        } else if width > height {
+            Layout::Landscape
+        } else {
+            Layout::Portrait
+        })
+    }
+}
+
+/// Unsigned EXIF rational, e.g. an exposure time of 1/160
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rational {
+    pub num: u32,
+    pub den: u32,
+}
+
+impl Rational {
+    /// Value as a float, infinite or NaN when the denominator is 0
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+}
+
+impl core::fmt::Display for Rational {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+/// Great-circle distance in meters between two decimal coordinates
+pub fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = libm::pow(libm::sin(d_lat / 2.0), 2.0)
+        + libm::cos(lat1.to_radians())
+            * libm::cos(lat2.to_radians())
+            * libm::pow(libm::sin(d_lon / 2.0), 2.0);
+    2.0 * EARTH_RADIUS_M * libm::asin(libm::sqrt(a))
+}
+
+/// Projects decimal coordinates to Web Mercator (EPSG:3857) meters, the
+/// latitude being clamped to ±85.05°
+pub fn web_mercator(lat: f64, lon: f64) -> (f64, f64) {
+    let lat = lat.clamp(-WEB_MERCATOR_MAX_LATITUDE, WEB_MERCATOR_MAX_LATITUDE);
+    let x = WEB_MERCATOR_RADIUS_M * lon.to_radians();
+    let y = WEB_MERCATOR_RADIUS_M
+        * libm::log(libm::tan(
+            core::f64::consts::FRAC_PI_4 + lat.to_radians() / 2.0,
+        ));
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(45.760_291_666)]
+    #[case(-4.855_822_222)]
+    #[case(0.0)]
+    fn has_gps_coord_round_trip(#[case] value: f64) {
+        let coord = GPSCoord::from_decimal(value);
+        assert!(coord.min < 60 && coord.sec < 60.0);
+        assert!((coord.to_decimal() - libm::fabs(value)).abs() < 1e-9);
+    }
+
+    #[rstest]
+    #[case(7, Hemisphere::North, Some(Season::Summer))]
+    #[case(7, Hemisphere::South, Some(Season::Winter))]
+    #[case(12, Hemisphere::North, Some(Season::Winter))]
+    #[case(3, Hemisphere::South, Some(Season::Autumn))]
+    #[case(13, Hemisphere::North, None)]
+    fn has_season(
+        #[case] month: u32,
+        #[case] hemisphere: Hemisphere,
+        #[case] expected: Option<Season>,
+    ) {
+        assert_eq!(Season::from_month(month, hemisphere), expected);
+    }
+
+    #[rstest]
+    #[case(4, Some(DayPart::Night))]
+    #[case(5, Some(DayPart::Morning))]
+    #[case(12, Some(DayPart::Afternoon))]
+    #[case(20, Some(DayPart::Evening))]
+    #[case(21, Some(DayPart::Night))]
+    #[case(24, None)]
+    fn has_day_part(#[case] hour: u32, #[case] expected: Option<DayPart>) {
+        assert_eq!(DayPart::from_hour(hour), expected);
+    }
+
+    #[rstest]
+    #[case(1, false, 0)]
+    #[case(2, true, 0)]
+    #[case(3, false, 180)]
+    #[case(4, true, 180)]
+    #[case(5, true, 270)]
+    #[case(6, false, 90)]
+    #[case(7, true, 90)]
+    #[case(8, false, 270)]
+    fn has_orientation_transform(
+        #[case] code: u16,
+        #[case] flip_horizontal: bool,
+        #[case] rotate_cw: u16,
+    ) {
+        let transform = Orientation::from_code(code).transform();
+        assert_eq!(
+            transform,
+            OrientationTransform {
+                flip_horizontal,
+                rotate_cw
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(9)]
+    #[case(u16::MAX)]
+    fn has_unknown_orientation_identity(#[case] code: u16) {
+        assert_eq!(Orientation::from_code(code), Orientation::Unknown);
+        assert!(Orientation::from_code(code).transform().is_identity());
+    }
+
+    #[rstest]
+    #[case(0, ExposureProgram::NotDefined)]
+    #[case(3, ExposureProgram::AperturePriority)]
+    #[case(8, ExposureProgram::Landscape)]
+    #[case(9, ExposureProgram::Unknown)]
+    fn has_exposure_program(#[case] code: u16, #[case] expected: ExposureProgram) {
+        assert_eq!(ExposureProgram::from_code(code), expected);
+    }
+
+    #[rstest]
+    #[case(0, SceneCaptureType::Standard, WhiteBalance::Auto)]
+    #[case(1, SceneCaptureType::Landscape, WhiteBalance::Manual)]
+    #[case(3, SceneCaptureType::Night, WhiteBalance::Unknown)]
+    #[case(42, SceneCaptureType::Unknown, WhiteBalance::Unknown)]
+    fn has_shooting_modes(
+        #[case] code: u16,
+        #[case] scene: SceneCaptureType,
+        #[case] white_balance: WhiteBalance,
+    ) {
+        assert_eq!(SceneCaptureType::from_code(code), scene);
+        assert_eq!(WhiteBalance::from_code(code), white_balance);
+    }
+
+    #[rstest]
+    #[case(Rational { num: 1, den: 160 }, 0.00625)]
+    #[case(Rational { num: 42, den: 10 }, 4.2)]
+    fn has_rational_value(#[case] value: Rational, #[case] expected: f64) {
+        assert!((value.to_f64() - expected).abs() < f64::EPSILON);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn has_rational_display() {
+        assert_eq!(Rational { num: 1, den: 160 }.to_string(), "1/160");
+    }
+
+    #[rstest]
+    #[case(45.7603, 4.8558, 45.7603, 4.8558, 0.0)]
+    #[case(48.8566, 2.3522, 45.7640, 4.8357, 391_500.0)]
+    #[case(0.0, 0.0, 0.0, 180.0, 20_015_115.0)]
+    fn has_haversine_distance(
+        #[case] lat1: f64,
+        #[case] lon1: f64,
+        #[case] lat2: f64,
+        #[case] lon2: f64,
+        #[case] expected: f64,
+    ) {
+        let d = haversine_distance(lat1, lon1, lat2, lon2);
+        assert!((d - expected).abs() < 1000.0, "{d} != {expected}");
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0, 0.0, 0.0)]
+    #[case(45.760_291_666, 4.855_822_222, 540_547.657, 5_742_018.651)]
+    #[case(90.0, 180.0, 20_037_508.343, 20_037_508.343)]
+    #[case(-90.0, -180.0, -20_037_508.343, -20_037_508.343)]
+    fn has_web_mercator(
+        #[case] lat: f64,
+        #[case] lon: f64,
+        #[case] expected_x: f64,
+        #[case] expected_y: f64,
+    ) {
+        let (x, y) = web_mercator(lat, lon);
+        assert!((x - expected_x).abs() < 0.01, "{x} != {expected_x}");
+        assert!((y - expected_y).abs() < 0.01, "{y} != {expected_y}");
+    }
+}
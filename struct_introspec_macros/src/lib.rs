@@ -2,9 +2,9 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, PathArguments, Type};
+use syn::{DeriveInput, PathArguments, Type, parse_macro_input};
 
-#[proc_macro_derive(DynamicGetSet)]
+#[proc_macro_derive(DynamicGetSet, attributes(exif))]
 pub fn dynamic_getset_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let struct_name = input.ident;
@@ -14,20 +14,18 @@ pub fn dynamic_getset_derive(input: TokenStream) -> TokenStream {
         _ => panic!("DynamicGetSet can only be used with structs"),
     };
 
+    // Every match arm carries its own trailing comma and is expanded without
+    // separator, keeping the catch-all arm valid for any number of fields
+
     // Generate match arms for `set_field_by_index`
     let set_index_match_arms = fields.iter().enumerate().filter_map(|(index, field)| {
         let field_name = field.ident.as_ref()?;
         let field_ty = &field.ty;
 
+        let body = set_field_body(field_name, field_ty);
+
         Some(quote! {
-            #index => {
-                if let Ok(value) = value.downcast::<#field_ty>() {
-                    self.#field_name = *value;
-                    Ok(())
-                } else {
-                    Err("Type mismatch for field")
-                }
-            }
+            #index => #body,
         })
     });
 
@@ -37,15 +35,10 @@ pub fn dynamic_getset_derive(input: TokenStream) -> TokenStream {
         let field_name_str = field_name.to_string();
         let field_ty = &field.ty;
 
+        let body = set_field_body(field_name, field_ty);
+
         Some(quote! {
-            #field_name_str => {
-                if let Ok(value) = value.downcast::<#field_ty>() {
-                    self.#field_name = *value;
-                    Ok(())
-                } else {
-                    Err("Type mismatch for field")
-                }
-            }
+            #field_name_str => #body,
         })
     });
 
@@ -53,20 +46,8 @@ pub fn dynamic_getset_derive(input: TokenStream) -> TokenStream {
     let get_name_match_arms = fields.iter().filter_map(|field| {
         let field_name = field.ident.as_ref()?;
         let field_name_str = field_name.to_string();
-        let field_ty = &field.ty;
 
-        // Detect if the type is Option<T>
-        let is_option = match field_ty {
-            Type::Path(type_path) if type_path.qself.is_none() => {
-                type_path.path.segments.last().is_some_and(|seg| {
-                    seg.ident == "Option"
-                        && matches!(seg.arguments, PathArguments::AngleBracketed(_))
-                })
-            }
-            _ => false,
-        };
-
-        if is_option {
+        if is_option(&field.ty) {
             // Special handling: return None if Option<T> is None
             Some(quote! {
                 #field_name_str => {
@@ -74,7 +55,7 @@ pub fn dynamic_getset_derive(input: TokenStream) -> TokenStream {
                         Some(inner) => Some(inner as &dyn std::any::Any),
                         None => None,
                     }
-                }
+                },
             })
         } else {
             // Normal field
@@ -84,6 +65,96 @@ pub fn dynamic_getset_derive(input: TokenStream) -> TokenStream {
         }
     });
 
+    // Generate (name, value) pairs for `fields`, in declaration order
+    let field_pairs = fields.iter().filter_map(|field| {
+        let field_name = field.ident.as_ref()?;
+        let field_name_str = field_name.to_string();
+
+        if is_option(&field.ty) {
+            Some(quote! {
+                (#field_name_str, self.#field_name.as_ref().map(|inner| inner as &dyn std::any::Any))
+            })
+        } else {
+            Some(quote! {
+                (#field_name_str, Some(&self.#field_name as &dyn std::any::Any))
+            })
+        }
+    });
+
+    // Generate pushes for `populated_field_names`, options only when set
+    let populated_pushes = fields.iter().filter_map(|field| {
+        let field_name = field.ident.as_ref()?;
+        let field_name_str = field_name.to_string();
+
+        if is_option(&field.ty) {
+            Some(quote! {
+                if self.#field_name.is_some() {
+                    names.push(#field_name_str);
+                }
+            })
+        } else {
+            Some(quote! {
+                names.push(#field_name_str);
+            })
+        }
+    });
+
+    // Generate pushes for `missing_required`, from the `#[exif(required)]`
+    // Option fields still set to None
+    let mut errors: Vec<syn::Error> = Vec::new();
+    let missing_pushes: Vec<_> = fields
+        .iter()
+        .filter_map(|field| {
+            let field_name = field.ident.as_ref()?;
+            let field_name_str = field_name.to_string();
+
+            match is_required(field) {
+                Ok(true) if is_option(&field.ty) => Some(quote! {
+                    if self.#field_name.is_none() {
+                        names.push(#field_name_str);
+                    }
+                }),
+                Ok(true) => {
+                    errors.push(syn::Error::new_spanned(
+                        field_name,
+                        "#[exif(required)] only applies to Option fields",
+                    ));
+                    None
+                }
+                Ok(false) => None,
+                Err(error) => {
+                    errors.push(error);
+                    None
+                }
+            }
+        })
+        .collect();
+    if let Some(error) = errors.into_iter().reduce(|mut all, error| {
+        all.combine(error);
+        all
+    }) {
+        return error.to_compile_error().into();
+    }
+
+    // Generate match arms for `clear_field_by_name`
+    let clear_name_match_arms = fields.iter().filter_map(|field| {
+        let field_name = field.ident.as_ref()?;
+        let field_name_str = field_name.to_string();
+
+        if is_option(&field.ty) {
+            Some(quote! {
+                #field_name_str => {
+                    self.#field_name = None;
+                    Ok(())
+                },
+            })
+        } else {
+            Some(quote! {
+                #field_name_str => Err(crate::FieldError::NotOptional(#field_name_str)),
+            })
+        }
+    });
+
     // Generate field names as a vector
     let field_names = fields.iter().filter_map(|field| {
         let field_name = field.ident.as_ref()?;
@@ -98,14 +169,14 @@ pub fn dynamic_getset_derive(input: TokenStream) -> TokenStream {
         impl DynamicGetSet for #struct_name {
             fn set_field_by_index(&mut self, index: usize, value: Box<dyn std::any::Any>) -> Result<(), &'static str> {
                 match index {
-                    #(#set_index_match_arms),*
+                    #(#set_index_match_arms)*
                     _ => Err("Invalid index"),
                 }
             }
 
             fn set_field_by_name(&mut self, name: &str, value: Box<dyn std::any::Any>) -> Result<(), &'static str> {
                 match name {
-                    #(#set_name_match_arms),*
+                    #(#set_name_match_arms)*
                     _ => Err("Invalid field name"),
                 }
             }
@@ -114,14 +185,106 @@ pub fn dynamic_getset_derive(input: TokenStream) -> TokenStream {
                 vec![#(#field_names),*]
             }
 
+            fn populated_field_names(&self) -> Vec<&'static str> {
+                #[allow(unused_mut)]
+                let mut names = Vec::new();
+                #(#populated_pushes)*
+                names
+            }
+
+            fn missing_required(&self) -> Vec<&'static str> {
+                #[allow(unused_mut)]
+                let mut names = Vec::new();
+                #(#missing_pushes)*
+                names
+            }
+
             fn get_value_by_field_name(&self, name: &str) -> Option<&dyn std::any::Any> {
                 match name {
                     #(#get_name_match_arms)*
                     _ => None,
                 }
             }
+
+            fn clear_field_by_name(&mut self, name: &str) -> Result<(), crate::FieldError> {
+                match name {
+                    #(#clear_name_match_arms)*
+                    _ => Err(crate::FieldError::UnknownField(name.to_string())),
+                }
+            }
+
+            fn fields(&self) -> Vec<(&'static str, Option<&dyn std::any::Any>)> {
+                vec![#(#field_pairs),*]
+            }
         }
     };
 
     TokenStream::from(expanded)
 }
+
+// Setter body downcasting the boxed value to the field type. A non-Option
+// field also accepts `Some(value)`, as boxed by `ExifAssignable::assign`.
+fn set_field_body(field_name: &syn::Ident, field_ty: &Type) -> proc_macro2::TokenStream {
+    if is_option(field_ty) {
+        return quote! {
+            {
+                if let Ok(value) = value.downcast::<#field_ty>() {
+                    self.#field_name = *value;
+                    Ok(())
+                } else {
+                    Err("Type mismatch for field")
+                }
+            }
+        };
+    }
+    quote! {
+        match value.downcast::<#field_ty>() {
+            Ok(value) => {
+                self.#field_name = *value;
+                Ok(())
+            }
+            Err(value) => match value.downcast::<::core::option::Option<#field_ty>>() {
+                Ok(value) => match *value {
+                    Some(value) => {
+                        self.#field_name = value;
+                        Ok(())
+                    }
+                    None => Err("Missing value for non optional field"),
+                },
+                Err(_) => Err("Type mismatch for field"),
+            },
+        }
+    }
+}
+
+// Detect the `#[exif(required)]` attribute, other `exif` arguments are errors
+fn is_required(field: &syn::Field) -> syn::Result<bool> {
+    let mut required = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("exif"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("required") {
+                required = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported exif attribute, expected `required`"))
+            }
+        })?;
+    }
+    Ok(required)
+}
+
+// Detect if the type is Option<T>
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last().is_some_and(|seg| {
+                seg.ident == "Option" && matches!(seg.arguments, PathArguments::AngleBracketed(_))
+            })
+        }
+        _ => false,
+    }
+}